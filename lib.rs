@@ -1,7 +1,84 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+/// Error type of the PSP22 standard interface
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    /// Custom error carrying the name of the contract's own error
+    Custom(String),
+    /// Not enough balance to fulfill the request
+    InsufficientBalance,
+    /// Not enough allowance to fulfill the request
+    InsufficientAllowance,
+    /// Recipient address is the zero address
+    ZeroRecipientAddress,
+    /// Sender address is the zero address
+    ZeroSenderAddress,
+    /// Receiving contract rejected the transfer
+    SafeTransferCheckFailed(String),
+}
+
+/// PSP22 fungible token standard
+///
+/// Selectors are derived from `PSP22::<message>` so they match the standard.
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Get the total supply of tokens
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    /// Get the balance of an account
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    /// Get the amount `spender` may still withdraw from `owner`
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    /// Transfer `value` tokens from the caller to `to`
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    /// Transfer `value` tokens from `from` to `to` using the caller's allowance
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Allow `spender` to withdraw up to `value` tokens from the caller
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error>;
+}
+
+/// Hook implemented by contracts that accept tokens sent with `transfer_checked`
+#[ink::trait_definition]
+pub trait PSP22Receiver {
+    /// Called after `value` tokens from `from` were credited to the receiver
+    ///
+    /// Returning an error (or not implementing the hook) reverts the transfer.
+    #[ink(message)]
+    fn on_received(
+        &mut self,
+        operator: AccountId,
+        from: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+}
+
 #[ink::contract]
 mod token_balance {
+    use crate::{PSP22Error, PSP22, PSP22Receiver};
+    use ink::codegen::TraitCallBuilder;
+    use ink::env::hash::Blake2x256;
+    use ink::prelude::{format, vec, vec::Vec};
     use ink::storage::Mapping;
 
     /// Custom error types for the token contract
@@ -16,18 +93,299 @@ mod token_balance {
         NotOwner,
         /// Invalid amount (zero or overflow)
         InvalidAmount,
+        /// Total supply would drop below zero (accounting invariant violated)
+        SupplyUnderflow,
+        /// Amount is above the confirmation threshold and must be staged
+        ConfirmationRequired,
+        /// No staged transfer exists with the given id
+        TransferNotFound,
+        /// Only the sender of a staged transfer can confirm or cancel it
+        NotSender,
+        /// The confirmation window for a staged transfer has passed
+        ConfirmationExpired,
+        /// Batch operation was called with no entries
+        EmptyBatch,
+        /// Batch has more entries than `max_batch_len` allows
+        BatchTooLarge,
+        /// Allowance too small for the requested `transfer_from`
+        InsufficientAllowance,
+        /// Beneficiary already has a vesting schedule that is not fully released
+        VestingExists,
+        /// Account has no vesting schedule
+        NoVestingSchedule,
+        /// No vested tokens are waiting to be released
+        NothingToRelease,
+        /// Fee in basis points must not exceed 10000
+        InvalidFee,
+        /// A guarded message was entered again before it finished
+        ReentrantCall,
+        /// The permit deadline has passed
+        PermitExpired,
+        /// Signature does not match the expected signer and message
+        InvalidSignature,
+        /// Minting has been permanently disabled
+        MintingFinished,
+        /// The allowance has passed its expiry block
+        AllowanceExpired,
+        /// Amount minted in one call exceeds `max_mint_per_tx`
+        MintLimitExceeded,
+        /// This contract's own token cannot be recovered with `recover_token`
+        CannotRecoverOwnToken,
+        /// The cross-contract transfer of the foreign token failed
+        RecoveryFailed,
+        /// The caller claimed from the faucet too recently
+        FaucetCooldown,
+        /// The account is blacklisted
+        Blacklisted,
+        /// Mint threshold must be between 1 and the number of co-owners
+        InvalidThreshold,
+        /// Only a co-owner can propose or approve mints
+        NotCoOwner,
+        /// No mint proposal exists with the given id
+        ProposalNotFound,
+        /// The mint proposal has already been executed
+        ProposalExecuted,
+        /// The co-owner has already approved this proposal
+        AlreadyApproved,
+        /// Mints need co-owner approval through `propose_mint`
+        QuorumRequired,
+        /// The sender transferred too recently
+        TransferCooldown,
+        /// Balance migration has been closed
+        MigrationClosed,
+        /// Decimals can only be changed before any tokens are minted
+        DecimalsLocked,
+        /// The contract owner cannot be blacklisted
+        CannotBlacklistOwner,
+        /// An account cannot approve itself as spender
+        SelfApproval,
+        /// Receiving contract rejected the transfer or has no receiver hook
+        TransferRejected,
+        /// Contract is paused and burns are disabled while paused
+        ContractPaused,
+        /// A non-zero allowance must be reset to zero before changing it
+        UnsafeApproval,
+        /// Signed batch nonce has already been used
+        NonceReused,
+        /// Recipient is the default (all-zero) account
+        InvalidRecipient,
     }
 
     /// Result type for the contract operations
     pub type Result<T> = core::result::Result<T, Error>;
 
+    impl From<Error> for PSP22Error {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::InsufficientBalance => PSP22Error::InsufficientBalance,
+                Error::InsufficientAllowance => PSP22Error::InsufficientAllowance,
+                Error::InvalidRecipient => PSP22Error::ZeroRecipientAddress,
+                Error::TransferRejected => PSP22Error::SafeTransferCheckFailed(format!("{:?}", error)),
+                other => PSP22Error::Custom(format!("{:?}", other)),
+            }
+        }
+    }
+
+    /// Default number of blocks a staged transfer can be confirmed in
+    const DEFAULT_CONFIRM_WINDOW: BlockNumber = 600;
+
+    /// Domain tag mixed into signed mint permits
+    const MINT_PERMIT_DOMAIN: &[u8] = b"token_balance::mint_with_permit";
+
+    /// Domain tag mixed into signed approval permits
+    const PERMIT_DOMAIN: &[u8] = b"token_balance::permit";
+
+    /// Domain tag mixed into signed batch transfers
+    const BATCH_TRANSFER_DOMAIN: &[u8] = b"token_balance::batch_transfer_signed";
+
+    /// Decimals used until the owner configures others
+    const DEFAULT_DECIMALS: u8 = 18;
+
+    /// Number of recent transfers kept per account by `transfer_history`
+    const MAX_TRANSFER_HISTORY: usize = 10;
+
+    /// Expiry block stored for allowances that never expire
+    const NO_EXPIRY: BlockNumber = BlockNumber::MAX;
+
+    /// Stored allowance: amount and the last block it can be used in
+    type AllowanceEntry = (u128, BlockNumber);
+
+    /// Basis points making up 100%
+    const MAX_BPS: u16 = 10_000;
+
+    /// Default maximum number of entries in a single batch call
+    const DEFAULT_MAX_BATCH_LEN: u32 = 100;
+
+    /// Version of the contract interface reported by `features`
+    const CONTRACT_VERSION: u16 = 1;
+
+    /// A large transfer waiting for its sender to confirm it
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PendingTransfer {
+        /// Account the tokens are sent from
+        pub from: AccountId,
+        /// Account the tokens are sent to
+        pub to: AccountId,
+        /// Amount locked for the transfer
+        pub amount: u128,
+        /// Last block in which the transfer can be confirmed
+        pub expires_at: BlockNumber,
+    }
+
+    /// Linear vesting schedule for tokens held by a beneficiary
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct VestingSchedule {
+        /// Total amount placed under vesting
+        pub total: u128,
+        /// Amount already released to the beneficiary
+        pub released: u128,
+        /// Block at which vesting starts
+        pub start_block: BlockNumber,
+        /// Number of blocks over which the total vests linearly
+        pub duration_blocks: BlockNumber,
+    }
+
+    impl VestingSchedule {
+        /// Amount vested as of `block`
+        fn vested_at(&self, block: BlockNumber) -> u128 {
+            let elapsed = block.saturating_sub(self.start_block);
+            if elapsed >= self.duration_blocks {
+                return self.total;
+            }
+
+            // Split the multiplication so `total * elapsed` cannot overflow
+            let elapsed = u128::from(elapsed);
+            let duration = u128::from(self.duration_blocks);
+            (self.total / duration) * elapsed + (self.total % duration) * elapsed / duration
+        }
+    }
+
+    /// One entry in an account's recent transfer history
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct TransferRecord {
+        /// The other side of the transfer
+        pub counterparty: AccountId,
+        /// Amount moved
+        pub amount: u128,
+        /// Block timestamp of the transfer
+        pub timestamp: Timestamp,
+        /// Whether the account received (rather than sent) the tokens
+        pub incoming: bool,
+    }
+
+    /// A mint waiting for enough co-owner approvals
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct MintProposal {
+        /// Account the tokens will be minted to
+        pub to: AccountId,
+        /// Amount to mint
+        pub amount: u128,
+        /// Co-owners who approved the proposal so far
+        pub approvals: Vec<AccountId>,
+        /// Whether the mint has been executed
+        pub executed: bool,
+    }
+
+    /// Breakdown of the parts of an account's balance that cannot be moved
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Encumbrances {
+        /// Amount locked in staged transfers awaiting confirmation
+        pub staged: u128,
+        /// Amount under vesting that has not been released yet
+        pub unvested: u128,
+        /// Amount locked by the owner, e.g. for staking or escrow
+        pub locked: u128,
+    }
+
+    impl Encumbrances {
+        /// Sum of all encumbered amounts
+        pub fn total(&self) -> u128 {
+            self.staged
+                .saturating_add(self.unvested)
+                .saturating_add(self.locked)
+        }
+    }
+
+    /// Which optional subsystems are currently enabled, returned by `features`
+    ///
+    /// Subsystems that are always available, such as vesting and permits,
+    /// are implied by `version`.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct FeatureFlags {
+        /// Version of the contract interface
+        pub version: u16,
+        /// New tokens can still be minted
+        pub minting: bool,
+        /// Mints need approvals from more than one co-owner
+        pub multisig_minting: bool,
+        /// The faucet hands out tokens
+        pub faucet: bool,
+        /// Transfers pay a fee to `fee_recipient`
+        pub transfer_fee: bool,
+        /// Burns send a share to the treasury
+        pub burn_fee: bool,
+        /// Large transfers must be staged and confirmed
+        pub transfer_confirmation: bool,
+        /// Accounts must wait between transfers
+        pub transfer_cooldown: bool,
+        /// Large per-block outflows pause the contract
+        pub circuit_breaker: bool,
+    }
+
+    /// Snapshot of the contract's configuration, returned by `get_config`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct ContractConfig {
+        /// Owner of the contract
+        pub owner: AccountId,
+        /// Total supply of tokens
+        pub total_supply: u128,
+        /// Number of decimals used to display amounts
+        pub decimals: u8,
+        /// Whether transfers are paused
+        pub paused: bool,
+        /// Whether minting has been permanently disabled
+        pub minting_finished: bool,
+        /// Largest amount a single minting call may create
+        pub max_mint_per_tx: Option<u128>,
+        /// Maximum number of entries in a batch call
+        pub max_batch_len: u32,
+        /// Transfers above this amount must be staged (0 disables)
+        pub confirm_threshold: u128,
+        /// Blocks a staged transfer stays confirmable
+        pub confirm_window: BlockNumber,
+        /// Per-block outflow that auto-pauses transfers
+        pub outflow_threshold: Option<u128>,
+        /// Transfer fee in basis points
+        pub fee_bps: u16,
+        /// Account credited with transfer fees
+        pub fee_recipient: AccountId,
+        /// Amount minted per faucet claim (0 disables)
+        pub faucet_amount: u128,
+        /// Blocks between faucet claims
+        pub faucet_cooldown_blocks: BlockNumber,
+        /// Share of each burn sent to the treasury, in basis points
+        pub burn_fee_bps: u16,
+        /// Account credited with burn fees
+        pub treasury: AccountId,
+    }
+
     /// Event emitted when tokens are minted
+    ///
+    /// `minter` is the account whose call performed the mint.
     #[ink(event)]
     pub struct TokensMinted {
         #[ink(topic)]
         pub to: AccountId,
-        #[ink(topic)]
         pub amount: u128,
+        #[ink(topic)]
+        pub minter: AccountId,
     }
 
     /// Event emitted when tokens are transferred
@@ -41,6 +399,166 @@ mod token_balance {
         pub amount: u128,
     }
 
+    /// Event emitted when tokens are burned and removed from the supply
+    #[ink(event)]
+    pub struct TokensBurned {
+        #[ink(topic)]
+        pub from: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when the owner moves tokens between accounts
+    #[ink(event)]
+    pub struct ForcedTransfer {
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+        pub amount: u128,
+        #[ink(topic)]
+        pub by: AccountId,
+    }
+
+    /// Event emitted when the owner withdraws tokens held by the contract itself
+    #[ink(event)]
+    pub struct EmergencyWithdrawal {
+        #[ink(topic)]
+        pub to: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when an allowance is set
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        pub owner: AccountId,
+        #[ink(topic)]
+        pub spender: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when transfers are paused
+    ///
+    /// `by` is the contract's own account when the circuit breaker tripped.
+    #[ink(event)]
+    pub struct Paused {
+        #[ink(topic)]
+        pub by: AccountId,
+    }
+
+    /// Event emitted when transfers are unpaused
+    #[ink(event)]
+    pub struct Unpaused {
+        #[ink(topic)]
+        pub by: AccountId,
+    }
+
+    /// Event emitted when a co-owner proposes a mint
+    #[ink(event)]
+    pub struct MintProposed {
+        #[ink(topic)]
+        pub id: u32,
+        #[ink(topic)]
+        pub proposer: AccountId,
+        pub to: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when a co-owner approves a mint proposal
+    #[ink(event)]
+    pub struct MintApproved {
+        #[ink(topic)]
+        pub id: u32,
+        #[ink(topic)]
+        pub approver: AccountId,
+    }
+
+    /// Event emitted when an account is allowed to manage the blacklist
+    #[ink(event)]
+    pub struct BlacklistManagerAdded {
+        #[ink(topic)]
+        pub manager: AccountId,
+    }
+
+    /// Event emitted when an account may no longer manage the blacklist
+    #[ink(event)]
+    pub struct BlacklistManagerRemoved {
+        #[ink(topic)]
+        pub manager: AccountId,
+    }
+
+    /// Event emitted when a foreign token held by the contract is recovered
+    #[ink(event)]
+    pub struct TokenRecovered {
+        #[ink(topic)]
+        pub token: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when a transfer fee is credited to the fee recipient
+    #[ink(event)]
+    pub struct FeeCollected {
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+        pub amount: u128,
+    }
+
+    /// Event emitted when tokens are burned to be redeemed on another chain
+    #[ink(event)]
+    pub struct BridgeBurn {
+        #[ink(topic)]
+        pub from: AccountId,
+        pub amount: u128,
+        #[ink(topic)]
+        pub dest_chain: u32,
+        #[ink(topic)]
+        pub dest_address: [u8; 32],
+    }
+
+    /// Event emitted when a large transfer is staged for confirmation
+    #[ink(event)]
+    pub struct TransferStaged {
+        #[ink(topic)]
+        pub id: u32,
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub to: AccountId,
+        pub amount: u128,
+        pub expires_at: BlockNumber,
+    }
+
+    /// Event emitted when a staged transfer is cancelled
+    #[ink(event)]
+    pub struct TransferCancelled {
+        #[ink(topic)]
+        pub id: u32,
+        #[ink(topic)]
+        pub from: AccountId,
+    }
+
+    /// Event emitted when tokens are placed under a vesting schedule
+    #[ink(event)]
+    pub struct VestingCreated {
+        #[ink(topic)]
+        pub beneficiary: AccountId,
+        pub total: u128,
+        pub start_block: BlockNumber,
+        pub duration_blocks: BlockNumber,
+    }
+
+    /// Event emitted when vested tokens are released to the beneficiary
+    #[ink(event)]
+    pub struct VestingReleased {
+        #[ink(topic)]
+        pub beneficiary: AccountId,
+        pub amount: u128,
+    }
+
     /// The token balance contract
     #[ink(storage)]
     pub struct TokenBalance {
@@ -51,12 +569,109 @@ mod token_balance {
         /// Owner of the contract (can mint tokens)
         owner: AccountId,
         //--- ASSIGNMENT --- Added storage for assignment requirements ---//
-        /// Allowances mapping (owner, spender) -> amount
-        allowances: Mapping<(AccountId, AccountId), u128>,
+        /// Allowances mapping (owner, spender) -> (amount, expiry block)
+        allowances: Mapping<(AccountId, AccountId), AllowanceEntry>,
         /// Pause state
         paused: bool,
         /// Blacklisted addresses
         blacklisted: Mapping<AccountId, bool>,
+        /// Transfers above this amount must be staged and confirmed (0 disables)
+        confirm_threshold: u128,
+        /// Number of blocks a staged transfer stays confirmable
+        confirm_window: BlockNumber,
+        /// Staged transfers awaiting confirmation, by id
+        pending_transfers: Mapping<u32, PendingTransfer>,
+        /// Total amount each account has locked in staged transfers
+        staged: Mapping<AccountId, u128>,
+        /// Id assigned to the next staged transfer
+        next_transfer_id: u32,
+        /// Maximum number of entries accepted by batch messages
+        max_batch_len: u32,
+        /// Vesting schedules by beneficiary
+        vesting: Mapping<AccountId, VestingSchedule>,
+        /// Fee taken on every transfer, in basis points
+        fee_bps: u16,
+        /// Account credited with transfer fees
+        fee_recipient: AccountId,
+        /// Reentrancy guard held while a state-mutating message runs
+        locked: bool,
+        /// Next nonce expected in a mint permit signed by each owner
+        mint_nonces: Mapping<AccountId, u64>,
+        /// Next nonce expected in an approval permit signed by each account
+        permit_nonces: Mapping<AccountId, u64>,
+        /// Whether minting has been permanently disabled
+        minting_finished: bool,
+        /// Largest amount a single minting call may create (`None` for no limit)
+        max_mint_per_tx: Option<u128>,
+        /// Block that `block_outflow` is being accumulated for
+        current_block: BlockNumber,
+        /// Amount transferred so far in `current_block`
+        block_outflow: u128,
+        /// Per-block outflow above which transfers auto-pause (`None` disables)
+        outflow_threshold: Option<u128>,
+        /// Cumulative amount of tokens ever burned
+        total_burned: u128,
+        /// Cumulative amount of tokens ever minted
+        total_minted: u128,
+        /// Amount minted per faucet claim (0 disables the faucet)
+        faucet_amount: u128,
+        /// Blocks an account must wait between faucet claims
+        faucet_cooldown_blocks: BlockNumber,
+        /// Block of each account's last faucet claim
+        last_claim: Mapping<AccountId, BlockNumber>,
+        /// Accounts allowed to manage the blacklist besides the owner
+        blacklist_managers: Mapping<AccountId, bool>,
+        /// Accounts that can still move funds while the contract is paused
+        pause_exempt: Mapping<AccountId, bool>,
+        /// Co-owners who can propose and approve quorum mints
+        owners: Vec<AccountId>,
+        /// Number of co-owner approvals needed to execute a mint proposal
+        mint_threshold: u8,
+        /// Mint proposals by id
+        mint_proposals: Mapping<u32, MintProposal>,
+        /// Id assigned to the next mint proposal
+        next_proposal_id: u32,
+        /// Blocks an account must wait between transfers (0 disables)
+        transfer_cooldown_blocks: BlockNumber,
+        /// Block of each account's last outgoing transfer
+        last_transfer: Mapping<AccountId, BlockNumber>,
+        /// Whether the owner can still seed balances from an old contract
+        migration_open: bool,
+        /// Last `MAX_TRANSFER_HISTORY` transfers of each account, oldest first
+        recent_transfers: Mapping<AccountId, Vec<TransferRecord>>,
+        /// Number of decimals used to display amounts
+        decimals: u8,
+        /// Amount of each account's balance locked by the owner
+        locked_balances: Mapping<AccountId, u128>,
+        /// Accounts whose holdings do not count as circulating supply
+        excluded_from_circulation: Mapping<AccountId, bool>,
+        /// Same accounts as `excluded_from_circulation`, kept so they can be iterated
+        excluded_accounts: Vec<AccountId>,
+        /// Every account with a non-zero balance by index, in no particular order
+        ///
+        /// Kept in sync by `set_balance`. Adding or removing a holder touches
+        /// a few storage cells no matter how many holders there are.
+        holders: Mapping<u32, AccountId>,
+        /// Index of each holder in `holders`
+        holder_index: Mapping<AccountId, u32>,
+        /// Number of entries in `holders`
+        holders_count: u32,
+        /// Share of each burn sent to the treasury instead, in basis points
+        burn_fee_bps: u16,
+        /// Account credited with burn fees
+        treasury: AccountId,
+        /// Accounts that neither pay nor cause the transfer fee
+        fee_exempt: Mapping<AccountId, bool>,
+        /// Spenders holding a non-zero allowance from each owner
+        spenders: Mapping<AccountId, Vec<AccountId>>,
+        /// Whether burns stay allowed while the contract is paused
+        burn_while_paused: bool,
+        /// Block timestamp of each account's last transfer, mint or burn
+        last_activity: Mapping<AccountId, Timestamp>,
+        /// Sister token whose balances `combined_balance_of` adds in
+        linked_token: Option<AccountId>,
+        /// Lowest batch nonce each account may still sign with
+        batch_nonces: Mapping<AccountId, u64>,
     }
 
     impl Default for TokenBalance {
@@ -78,271 +693,4783 @@ mod token_balance {
                 allowances: Mapping::new(),
                 paused: false,
                 blacklisted: Mapping::new(),
+                confirm_threshold: 0,
+                confirm_window: DEFAULT_CONFIRM_WINDOW,
+                pending_transfers: Mapping::new(),
+                staged: Mapping::new(),
+                next_transfer_id: 0,
+                max_batch_len: DEFAULT_MAX_BATCH_LEN,
+                vesting: Mapping::new(),
+                fee_bps: 0,
+                fee_recipient: caller,
+                locked: false,
+                mint_nonces: Mapping::new(),
+                permit_nonces: Mapping::new(),
+                minting_finished: false,
+                max_mint_per_tx: None,
+                current_block: 0,
+                block_outflow: 0,
+                outflow_threshold: None,
+                total_burned: 0,
+                total_minted: 0,
+                faucet_amount: 0,
+                faucet_cooldown_blocks: 0,
+                last_claim: Mapping::new(),
+                blacklist_managers: Mapping::new(),
+                pause_exempt: Mapping::new(),
+                owners: vec![caller],
+                mint_threshold: 1,
+                mint_proposals: Mapping::new(),
+                next_proposal_id: 0,
+                transfer_cooldown_blocks: 0,
+                last_transfer: Mapping::new(),
+                migration_open: true,
+                recent_transfers: Mapping::new(),
+                decimals: DEFAULT_DECIMALS,
+                locked_balances: Mapping::new(),
+                excluded_from_circulation: Mapping::new(),
+                excluded_accounts: Vec::new(),
+                holders: Mapping::new(),
+                holder_index: Mapping::new(),
+                holders_count: 0,
+                burn_fee_bps: 0,
+                treasury: caller,
+                fee_exempt: Mapping::new(),
+                spenders: Mapping::new(),
+                burn_while_paused: true,
+                last_activity: Mapping::new(),
+                linked_token: None,
+                batch_nonces: Mapping::new(),
             }
         }
 
+        /// Creates a new token contract whose quorum mints need `mint_threshold`
+        /// approvals from `owners`
+        ///
+        /// With a threshold above 1, `propose_mint` is the only way to mint:
+        /// the owner's single-key minting messages and the faucet return
+        /// `QuorumRequired`.
+        #[ink(constructor)]
+        pub fn new_with_owners(mut owners: Vec<AccountId>, mint_threshold: u8) -> Result<Self> {
+            owners.sort();
+            owners.dedup();
+
+            if mint_threshold == 0 || usize::from(mint_threshold) > owners.len() {
+                return Err(Error::InvalidThreshold);
+            }
+
+            let mut contract = Self::new();
+            contract.owners = owners;
+            contract.mint_threshold = mint_threshold;
+            Ok(contract)
+        }
+
+        /// Creates a new token contract and mints `initial_supply` to the deployer
+        #[ink(constructor)]
+        pub fn new_with_supply(initial_supply: u128) -> Result<Self> {
+            let mut contract = Self::new();
+            if initial_supply > 0 {
+                let caller = contract.env().caller();
+                contract.mint_to(caller, initial_supply)?;
+            }
+            Ok(contract)
+        }
+
         /// Mint new tokens to an account (only owner can do this)
+        ///
+        /// Returns the recipient's balance after the mint.
         #[ink(message)]
-        pub fn mint(&mut self, to: AccountId, amount: u128) -> Result<()> {
+        pub fn mint(&mut self, to: AccountId, amount: u128) -> Result<u128> {
             // Check if caller is the owner
             if self.env().caller() != self.owner {
                 return Err(Error::NotOwner);
             }
 
+            self.ensure_single_owner_minting()?;
+
             // Check for valid amount
             if amount == 0 {
                 return Err(Error::InvalidAmount);
             }
 
-            // Check for overflow
-            let current_balance = self.balances.get(to).unwrap_or(0);
-            let new_balance = current_balance.checked_add(amount)
-                .ok_or(Error::InvalidAmount)?;
-
-            // Update balances and total supply
-            self.balances.insert(to, &new_balance);
-            self.total_supply = self.total_supply.checked_add(amount)
-                .ok_or(Error::InvalidAmount)?;
+            self.ensure_mint_limit(amount)?;
 
-            // Emit event
-            self.env().emit_event(TokensMinted { to, amount });
+            self.mint_to(to, amount)?;
 
-            Ok(())
+            Ok(self.balance_of(to))
         }
 
-        /// Get the balance of an account
+        /// Mint to an account and set its allowance for `spender` in one call (owner only)
+        ///
+        /// Saves onboarding users a separate `approve` transaction. Emits both
+        /// `TokensMinted` and `Approval`.
         #[ink(message)]
-        pub fn balance_of(&self, account: AccountId) -> u128 {
-            self.balances.get(account).unwrap_or(0)
-        }
+        pub fn mint_and_approve(
+            &mut self,
+            to: AccountId,
+            amount: u128,
+            spender: AccountId,
+            allowance: u128,
+        ) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
 
-        /// Get the total supply of tokens
-        #[ink(message)]
-        pub fn total_supply(&self) -> u128 {
-            self.total_supply
-        }
+            self.ensure_single_owner_minting()?;
 
-        /// Get the owner of the contract
-        #[ink(message)]
-        pub fn owner(&self) -> AccountId {
-            self.owner
-        }
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
 
-        /// Transfer tokens from caller to another account
-        #[ink(message)]
-        pub fn transfer(&mut self, to: AccountId, amount: u128) -> Result<()> {
-            let caller = self.env().caller();
+            self.ensure_mint_limit(amount)?;
+            self.mint_to(to, amount)?;
 
-            //--- ASSIGNMENT --- Check pause state and blacklist ---//
-            if self.paused {
-                return Err(Error::InvalidAmount); // Using InvalidAmount as pause error
-            }
+            self.set_allowance(to, spender, allowance, NO_EXPIRY);
+            self.env().emit_event(Approval {
+                owner: to,
+                spender,
+                amount: allowance,
+            });
 
-            if self.blacklisted.get(caller).unwrap_or(false) || self.blacklisted.get(to).unwrap_or(false) {
-                return Err(Error::InvalidAmount); // Using InvalidAmount as blacklist error
-            }
+            Ok(())
+        }
 
-            // Check if transferring to self
-            if caller == to {
-                return Err(Error::TransferToSelf);
+        /// Propose a mint that executes once `mint_threshold` co-owners approve it
+        ///
+        /// The proposer's approval is counted right away. Returns the proposal id.
+        #[ink(message)]
+        pub fn propose_mint(&mut self, to: AccountId, amount: u128) -> Result<u32> {
+            let caller = self.env().caller();
+            if !self.owners.contains(&caller) {
+                return Err(Error::NotCoOwner);
             }
 
-            // Check for valid amount
             if amount == 0 {
                 return Err(Error::InvalidAmount);
             }
 
-            // Get current balances
-            let caller_balance = self.balances.get(caller).unwrap_or(0);
-            let to_balance = self.balances.get(to).unwrap_or(0);
-
-            // Check if caller has sufficient balance
-            if caller_balance < amount {
-                return Err(Error::InsufficientBalance);
-            }
-
-            // Calculate new balances
-            let new_caller_balance = caller_balance.saturating_sub(amount);
-            let new_to_balance = to_balance.checked_add(amount)
-                .ok_or(Error::InvalidAmount)?;
-
-            // Update balances
-            self.balances.insert(caller, &new_caller_balance);
-            self.balances.insert(to, &new_to_balance);
+            let id = self.next_proposal_id;
+            self.next_proposal_id = id.checked_add(1).ok_or(Error::InvalidAmount)?;
+            self.mint_proposals.insert(id, &MintProposal {
+                to,
+                amount,
+                approvals: Vec::new(),
+                executed: false,
+            });
 
-            // Emit event
-            self.env().emit_event(TokensTransferred {
-                from: caller,
+            self.env().emit_event(MintProposed {
+                id,
+                proposer: caller,
                 to,
                 amount,
             });
 
-            Ok(())
-        }
+            self.approve_mint(id)?;
 
-        /// Get the caller's own balance
-        #[ink(message)]
-        pub fn my_balance(&self) -> u128 {
-            self.balance_of(self.env().caller())
+            Ok(id)
         }
 
-        //--- ASSIGNMENT --- Added functionalities for assignment requirements ---//
-
-        /// Burn tokens from caller's account
+        /// Approve a mint proposal (co-owners only), executing it at the threshold
         #[ink(message)]
-        pub fn burn(&mut self, amount: u128) -> Result<()> {
+        pub fn approve_mint(&mut self, proposal_id: u32) -> Result<()> {
             let caller = self.env().caller();
-            let caller_balance = self.balances.get(caller).unwrap_or(0);
+            if !self.owners.contains(&caller) {
+                return Err(Error::NotCoOwner);
+            }
 
-            if amount == 0 {
-                return Err(Error::InvalidAmount);
+            let mut proposal = self.mint_proposals.get(proposal_id).ok_or(Error::ProposalNotFound)?;
+            if proposal.executed {
+                return Err(Error::ProposalExecuted);
+            }
+            if proposal.approvals.contains(&caller) {
+                return Err(Error::AlreadyApproved);
             }
 
-            if caller_balance < amount {
-                return Err(Error::InsufficientBalance);
+            proposal.approvals.push(caller);
+            self.env().emit_event(MintApproved {
+                id: proposal_id,
+                approver: caller,
+            });
+
+            if proposal.approvals.len() >= usize::from(self.mint_threshold) {
+                self.ensure_mint_limit(proposal.amount)?;
+                self.mint_to(proposal.to, proposal.amount)?;
+                proposal.executed = true;
             }
 
-            let new_balance = caller_balance.saturating_sub(amount);
-            self.balances.insert(caller, &new_balance);
-            self.total_supply = self.total_supply.saturating_sub(amount);
+            self.mint_proposals.insert(proposal_id, &proposal);
 
             Ok(())
         }
 
-        /// Check allowance for spender
+        /// Get a mint proposal by id
         #[ink(message)]
-        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
-            self.allowances.get((owner, spender)).unwrap_or(0)
+        pub fn mint_proposal(&self, proposal_id: u32) -> Option<MintProposal> {
+            self.mint_proposals.get(proposal_id)
         }
 
-        /// Approve spender to spend tokens
+        /// Get the co-owners who can propose and approve quorum mints
         #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, amount: u128) -> Result<()> {
-            let caller = self.env().caller();
-            self.allowances.insert((caller, spender), &amount);
-            Ok(())
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.owners.clone()
         }
 
-        /// Transfer tokens using allowance
+        /// Get the number of co-owner approvals needed to execute a mint
         #[ink(message)]
-        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
-            let caller = self.env().caller();
-            let allowance = self.allowances.get((from, caller)).unwrap_or(0);
+        pub fn mint_threshold(&self) -> u8 {
+            self.mint_threshold
+        }
+
+        /// Mint tokens authorized off-chain by the owner, submitted by anyone
+        ///
+        /// `signature` is the owner's sr25519 signature over the blake2x256 hash of
+        /// `(MINT_PERMIT_DOMAIN, contract, to, amount, deadline, nonce)`, where
+        /// `nonce` is `nonce_of(owner)`. Each permit can be used once and only
+        /// until `deadline` (milliseconds). Relies on `sr25519_verify`, which is
+        /// part of the unstable contracts interface.
+        #[ink(message)]
+        pub fn mint_with_permit(
+            &mut self,
+            to: AccountId,
+            amount: u128,
+            deadline: Timestamp,
+            signature: [u8; 64],
+        ) -> Result<()> {
+            self.ensure_single_owner_minting()?;
+
+            if self.env().block_timestamp() > deadline {
+                return Err(Error::PermitExpired);
+            }
 
             if amount == 0 {
                 return Err(Error::InvalidAmount);
             }
 
-            if allowance < amount {
-                return Err(Error::InsufficientBalance);
+            let owner = self.owner;
+            let nonce = self.nonce_of(owner);
+            let message = self.env().hash_encoded::<Blake2x256, _>(&(
+                MINT_PERMIT_DOMAIN,
+                self.env().account_id(),
+                to,
+                amount,
+                deadline,
+                nonce,
+            ));
+            self.env().sr25519_verify(&signature, &message, owner.as_ref())
+                .map_err(|_| Error::InvalidSignature)?;
+
+            // Consume the nonce so the permit cannot be replayed
+            self.mint_nonces.insert(owner, &nonce.checked_add(1).ok_or(Error::InvalidAmount)?);
+
+            self.ensure_mint_limit(amount)?;
+
+            self.mint_to(to, amount)
+        }
+
+        /// Get the next mint permit nonce for an owner
+        #[ink(message)]
+        pub fn nonce_of(&self, owner: AccountId) -> u64 {
+            self.mint_nonces.get(owner).unwrap_or(0)
+        }
+
+        /// Mint the same amount of new tokens to every recipient (owner only)
+        ///
+        /// Tokens are newly minted, not taken from the caller's balance, and a
+        /// `TokensMinted` event is emitted per recipient.
+        #[ink(message)]
+        pub fn airdrop(&mut self, recipients: Vec<AccountId>, amount_each: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
             }
 
-            let from_balance = self.balances.get(from).unwrap_or(0);
-            if from_balance < amount {
-                return Err(Error::InsufficientBalance);
+            self.ensure_single_owner_minting()?;
+
+            self.ensure_batch_len(recipients.len())?;
+
+            if amount_each == 0 {
+                return Err(Error::InvalidAmount);
             }
 
-            let new_from_balance = from_balance.saturating_sub(amount);
-            let new_to_balance = self.balances.get(to).unwrap_or(0).checked_add(amount)
+            // Account for the whole airdrop in the supply once, before crediting anyone
+            let total_amount = amount_each.checked_mul(recipients.len() as u128)
                 .ok_or(Error::InvalidAmount)?;
-            let new_allowance = allowance.saturating_sub(amount);
+            self.ensure_mint_limit(total_amount)?;
+            self.add_supply(total_amount)?;
 
-            self.balances.insert(from, &new_from_balance);
-            self.balances.insert(to, &new_to_balance);
-            self.allowances.insert((from, caller), &new_allowance);
+            for to in recipients {
+                self.credit_minted(to, amount_each)?;
+            }
 
             Ok(())
         }
 
-        /// Pause all transfers (owner only)
+        /// Seed balances ported from an old contract (owner only, until migration closes)
+        ///
+        /// Each entry is credited on top of any existing balance and counted in
+        /// `total_supply` and `total_minted`, so repeated entries add up. The
+        /// combined amount counts against `max_mint_per_tx` like any batch mint.
         #[ink(message)]
-        pub fn pause(&mut self) -> Result<()> {
+        pub fn migrate_balances(&mut self, entries: Vec<(AccountId, u128)>) -> Result<()> {
             if self.env().caller() != self.owner {
                 return Err(Error::NotOwner);
             }
-            self.paused = true;
+
+            if !self.migration_open {
+                return Err(Error::MigrationClosed);
+            }
+
+            self.ensure_single_owner_minting()?;
+
+            self.ensure_batch_len(entries.len())?;
+
+            let mut total_amount: u128 = 0;
+            for (_, amount) in &entries {
+                if *amount == 0 {
+                    return Err(Error::InvalidAmount);
+                }
+                total_amount = total_amount.checked_add(*amount)
+                    .ok_or(Error::InvalidAmount)?;
+            }
+            self.ensure_mint_limit(total_amount)?;
+            self.add_supply(total_amount)?;
+
+            for (account, amount) in entries {
+                self.credit_minted(account, amount)?;
+            }
+
             Ok(())
         }
 
-        /// Unpause all transfers (owner only)
+        /// Permanently close balance migration (owner only)
         #[ink(message)]
-        pub fn unpause(&mut self) -> Result<()> {
+        pub fn close_migration(&mut self) -> Result<()> {
             if self.env().caller() != self.owner {
                 return Err(Error::NotOwner);
             }
-            self.paused = false;
+            self.migration_open = false;
             Ok(())
         }
 
-        /// Check if contract is paused
+        /// Check if the owner can still seed migrated balances
         #[ink(message)]
-        pub fn is_paused(&self) -> bool {
-            self.paused
+        pub fn is_migration_open(&self) -> bool {
+            self.migration_open
         }
 
-        /// Blacklist an address (owner only)
+        /// Permanently disable minting (owner only)
+        ///
+        /// This cannot be undone. Transfers and burns keep working.
         #[ink(message)]
-        pub fn blacklist(&mut self, account: AccountId) -> Result<()> {
+        pub fn finish_minting(&mut self) -> Result<()> {
             if self.env().caller() != self.owner {
                 return Err(Error::NotOwner);
             }
-            self.blacklisted.insert(account, &true);
+            self.minting_finished = true;
             Ok(())
         }
 
-        /// Remove from blacklist (owner only)
+        /// Check if minting has been permanently disabled
         #[ink(message)]
-        pub fn unblacklist(&mut self, account: AccountId) -> Result<()> {
+        pub fn is_minting_finished(&self) -> bool {
+            self.minting_finished
+        }
+
+        /// Mint `faucet_amount` to the caller, at most once per cooldown window
+        #[ink(message)]
+        pub fn faucet_claim(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            let now = self.env().block_number();
+
+            // A zero faucet amount means the faucet is disabled
+            if self.faucet_amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            // The owner alone sets the faucet amount, so it is a single-key mint too
+            self.ensure_single_owner_minting()?;
+
+            if let Some(last) = self.last_claim.get(caller) {
+                if now < last.saturating_add(self.faucet_cooldown_blocks) {
+                    return Err(Error::FaucetCooldown);
+                }
+            }
+
+            self.ensure_mint_limit(self.faucet_amount)?;
+            self.mint_to(caller, self.faucet_amount)?;
+            self.last_claim.insert(caller, &now);
+
+            Ok(())
+        }
+
+        /// Configure the faucet amount and cooldown (owner only, amount 0 disables)
+        #[ink(message)]
+        pub fn set_faucet(&mut self, amount: u128, cooldown_blocks: BlockNumber) -> Result<()> {
             if self.env().caller() != self.owner {
                 return Err(Error::NotOwner);
             }
-            self.blacklisted.insert(account, &false);
+            self.faucet_amount = amount;
+            self.faucet_cooldown_blocks = cooldown_blocks;
             Ok(())
         }
 
-        /// Check if address is blacklisted
+        /// Get the amount minted per faucet claim
         #[ink(message)]
-        pub fn is_blacklisted(&self, account: AccountId) -> bool {
-            self.blacklisted.get(account).unwrap_or(false)
+        pub fn faucet_amount(&self) -> u128 {
+            self.faucet_amount
         }
 
-        /// Batch transfer to multiple addresses
+        /// Get the blocks an account must wait between faucet claims
         #[ink(message)]
-        pub fn batch_transfer(&mut self, recipients: Vec<(AccountId, u128)>) -> Result<()> {
-            let caller = self.env().caller();
-            let caller_balance = self.balances.get(caller).unwrap_or(0);
+        pub fn faucet_cooldown_blocks(&self) -> BlockNumber {
+            self.faucet_cooldown_blocks
+        }
 
-            // Check if caller has enough balance for all transfers
-            let total_amount: u128 = recipients.iter().map(|(_, amount)| amount).sum();
-            if caller_balance < total_amount {
-                return Err(Error::InsufficientBalance);
+        /// Set the largest amount a single minting call may create (owner only)
+        ///
+        /// `None` removes the limit. Batch mints count their combined total.
+        #[ink(message)]
+        pub fn set_max_mint_per_tx(&mut self, limit: Option<u128>) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
             }
+            self.max_mint_per_tx = limit;
+            Ok(())
+        }
 
-            // Check for zero amounts
-            for (_, amount) in &recipients {
-                if *amount == 0 {
-                    return Err(Error::InvalidAmount);
-                }
+        /// Get the largest amount a single minting call may create
+        #[ink(message)]
+        pub fn max_mint_per_tx(&self) -> Option<u128> {
+            self.max_mint_per_tx
+        }
+
+        /// Get the balance of an account
+        #[ink(message)]
+        pub fn balance_of(&self, account: AccountId) -> u128 {
+            self.balances.get(account).unwrap_or(0)
+        }
+
+        /// Get the total supply of tokens
+        #[ink(message)]
+        pub fn total_supply(&self) -> u128 {
+            self.total_supply
+        }
+
+        /// Get the number of decimals used to display amounts
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            self.decimals
+        }
+
+        /// Set the number of decimals (owner only, before any tokens are minted)
+        #[ink(message)]
+        pub fn set_decimals(&mut self, decimals: u8) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if self.total_minted > 0 {
+                return Err(Error::DecimalsLocked);
             }
+            self.decimals = decimals;
+            Ok(())
+        }
 
-            // Perform all transfers
-            for (to, amount) in recipients {
-                if caller == to {
-                    return Err(Error::TransferToSelf);
+        /// Get the raw total supply together with the decimals to scale it by
+        ///
+        /// The human-readable supply is `supply / 10^decimals`.
+        #[ink(message)]
+        pub fn total_supply_scaled(&self) -> (u128, u8) {
+            (self.total_supply, self.decimals)
+        }
+
+        /// Get an account's raw balance together with the decimals to scale it by
+        #[ink(message)]
+        pub fn balance_of_scaled(&self, account: AccountId) -> (u128, u8) {
+            (self.balance_of(account), self.decimals)
+        }
+
+        /// Get the total supply minus the balances of accounts excluded from circulation
+        #[ink(message)]
+        pub fn circulating_supply(&self) -> u128 {
+            let excluded: u128 = self.excluded_accounts.iter()
+                .map(|account| self.balance_of(*account))
+                .fold(0, u128::saturating_add);
+            self.total_supply.saturating_sub(excluded)
+        }
+
+        /// Exclude an account's holdings (e.g. treasury) from circulating supply (owner only)
+        #[ink(message)]
+        pub fn exclude_from_circulation(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if !self.is_excluded_from_circulation(account) {
+                self.excluded_from_circulation.insert(account, &true);
+                self.excluded_accounts.push(account);
+            }
+            Ok(())
+        }
+
+        /// Count an account's holdings as circulating supply again (owner only)
+        #[ink(message)]
+        pub fn include_in_circulation(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if self.is_excluded_from_circulation(account) {
+                self.excluded_from_circulation.remove(account);
+                self.excluded_accounts.retain(|excluded| *excluded != account);
+            }
+            Ok(())
+        }
+
+        /// Check if an account's holdings are excluded from circulating supply
+        #[ink(message)]
+        pub fn is_excluded_from_circulation(&self, account: AccountId) -> bool {
+            self.excluded_from_circulation.get(account).unwrap_or(false)
+        }
+
+        /// Get the cumulative amount of tokens ever burned
+        #[ink(message)]
+        pub fn total_burned(&self) -> u128 {
+            self.total_burned
+        }
+
+        /// Get the cumulative amount of tokens ever minted
+        ///
+        /// Never decreases; `total_minted() - total_burned() == total_supply()`.
+        #[ink(message)]
+        pub fn total_minted(&self) -> u128 {
+            self.total_minted
+        }
+
+        /// Get the contract's configuration and caps in one call
+        #[ink(message)]
+        pub fn get_config(&self) -> ContractConfig {
+            ContractConfig {
+                owner: self.owner,
+                total_supply: self.total_supply,
+                decimals: self.decimals,
+                paused: self.paused,
+                minting_finished: self.minting_finished,
+                max_mint_per_tx: self.max_mint_per_tx,
+                max_batch_len: self.max_batch_len,
+                confirm_threshold: self.confirm_threshold,
+                confirm_window: self.confirm_window,
+                outflow_threshold: self.outflow_threshold,
+                fee_bps: self.fee_bps,
+                fee_recipient: self.fee_recipient,
+                faucet_amount: self.faucet_amount,
+                faucet_cooldown_blocks: self.faucet_cooldown_blocks,
+                burn_fee_bps: self.burn_fee_bps,
+                treasury: self.treasury,
+            }
+        }
+
+        /// Get the interface version and which optional subsystems are enabled
+        #[ink(message)]
+        pub fn features(&self) -> FeatureFlags {
+            FeatureFlags {
+                version: CONTRACT_VERSION,
+                minting: !self.minting_finished,
+                multisig_minting: self.mint_threshold > 1,
+                faucet: self.faucet_amount > 0,
+                transfer_fee: self.fee_bps > 0,
+                burn_fee: self.burn_fee_bps > 0,
+                transfer_confirmation: self.confirm_threshold > 0,
+                transfer_cooldown: self.transfer_cooldown_blocks > 0,
+                circuit_breaker: self.outflow_threshold.is_some(),
+            }
+        }
+
+        /// Get the owner of the contract
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Transfer tokens from caller to another account
+        ///
+        /// Amounts above `confirm_threshold` must go through `stage_transfer` instead.
+        /// If a fee is configured, `fee_bps` of the amount goes to `fee_recipient`
+        /// and `to` receives the remainder, unless either side is fee-exempt.
+        #[ink(message)]
+        pub fn transfer(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            self.non_reentrant(|this| this.transfer_unguarded(to, amount))?;
+            Ok(())
+        }
+
+        /// Transfer tokens, making sure a contract recipient can handle them
+        ///
+        /// If `to` is a contract, its `PSP22Receiver::on_received` hook is called
+        /// with `data` and the amount credited after fees, and the transfer is
+        /// reverted unless the hook succeeds. For plain accounts this behaves
+        /// like `transfer`.
+        #[ink(message)]
+        pub fn transfer_checked(&mut self, to: AccountId, amount: u128, data: Vec<u8>) -> Result<()> {
+            self.non_reentrant(|this| {
+                let received = this.transfer_unguarded(to, amount)?;
+
+                if this.env().is_contract(&to) {
+                    let caller = this.env().caller();
+                    let mut receiver: ink::contract_ref!(PSP22Receiver) = to.into();
+                    match receiver.call_mut().on_received(caller, caller, received, data).try_invoke() {
+                        Ok(Ok(Ok(()))) => {}
+                        _ => return Err(Error::TransferRejected),
+                    }
                 }
 
-                let to_balance = self.balances.get(to).unwrap_or(0);
-                let new_to_balance = to_balance.checked_add(amount)
-                    .ok_or(Error::InvalidAmount)?;
+                Ok(())
+            })
+        }
+
+        /// Check whether `from` could transfer `amount` to `to` right now
+        ///
+        /// Runs the same checks as `transfer` without changing state and returns
+        /// the error `transfer` would fail with, if any.
+        #[ink(message)]
+        pub fn can_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.check_transfer(from, to, amount)
+        }
+
+        /// Get an account's most recent sent and received transfers, oldest first
+        ///
+        /// At most `MAX_TRANSFER_HISTORY` records are kept per account, which
+        /// adds roughly 600 bytes of storage (and deposit) for an active account.
+        #[ink(message)]
+        pub fn transfer_history(&self, account: AccountId) -> Vec<TransferRecord> {
+            self.recent_transfers.get(account).unwrap_or_default()
+        }
+
+        /// Get the block timestamp of an account's last transfer, mint or burn
+        #[ink(message)]
+        pub fn last_activity_of(&self, account: AccountId) -> Option<Timestamp> {
+            self.last_activity.get(account)
+        }
+
+        /// Get the caller's own balance
+        #[ink(message)]
+        pub fn my_balance(&self) -> u128 {
+            self.balance_of(self.env().caller())
+        }
+
+        /// Get up to `limit` holders starting at index `start`
+        ///
+        /// The order is not stable: removing a holder moves the last one into its slot.
+        #[ink(message)]
+        pub fn holders_page(&self, start: u32, limit: u32) -> Vec<AccountId> {
+            let end = start.saturating_add(limit).min(self.holders_count);
+            (start..end)
+                .filter_map(|index| self.holders.get(index))
+                .collect()
+        }
+
+        /// Get the number of accounts holding a non-zero balance
+        #[ink(message)]
+        pub fn holders_count(&self) -> u32 {
+            self.holders_count
+        }
+
+        /// Get the balance held at the contract's own address
+        #[ink(message)]
+        pub fn contract_balance(&self) -> u128 {
+            self.balance_of(self.env().account_id())
+        }
+
+        /// Set or clear the sister token used by `combined_balance_of` (owner only)
+        #[ink(message)]
+        pub fn set_linked_token(&mut self, token: Option<AccountId>) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.linked_token = token;
+            Ok(())
+        }
+
+        /// Get the sister token used by `combined_balance_of`
+        #[ink(message)]
+        pub fn linked_token(&self) -> Option<AccountId> {
+            self.linked_token
+        }
+
+        /// Get an account's balance here plus its balance on the linked token
+        ///
+        /// If no token is linked, or the call to it fails, only the local
+        /// balance is returned.
+        #[ink(message)]
+        pub fn combined_balance_of(&self, account: AccountId) -> u128 {
+            let local = self.balance_of(account);
+
+            let Some(token) = self.linked_token else {
+                return local;
+            };
+
+            let linked: ink::contract_ref!(PSP22) = token.into();
+            match linked.call().balance_of(account).try_invoke() {
+                Ok(Ok(balance)) => local.saturating_add(balance),
+                _ => local,
+            }
+        }
+
+        //--- ASSIGNMENT --- Added functionalities for assignment requirements ---//
+
+        /// Burn tokens from caller's account
+        ///
+        /// If a burn fee is set, that share goes to the treasury and only the
+        /// remainder is removed from the supply.
+        #[ink(message)]
+        pub fn burn(&mut self, amount: u128) -> Result<()> {
+            self.non_reentrant(|this| this.burn_with_fee(amount))?;
+            Ok(())
+        }
 
-                self.balances.insert(to, &new_to_balance);
+        /// Burn tokens from caller's account for redemption on another chain
+        ///
+        /// Bridges watch the `BridgeBurn` event and mint to `dest_address` on
+        /// `dest_chain`. The burn fee applies as for `burn`, and the event
+        /// carries only the amount actually burned.
+        #[ink(message)]
+        pub fn burn_for_bridge(&mut self, amount: u128, dest_chain: u32, dest_address: [u8; 32]) -> Result<()> {
+            let burned = self.non_reentrant(|this| this.burn_with_fee(amount))?;
+
+            self.env().emit_event(BridgeBurn {
+                from: self.env().caller(),
+                amount: burned,
+                dest_chain,
+                dest_address,
+            });
+
+            Ok(())
+        }
+
+        /// Check allowance for spender
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
+            self.allowances.get((owner, spender)).map(|(amount, _)| amount).unwrap_or(0)
+        }
+
+        /// Get every spender holding a non-zero allowance from `owner`, with its amount
+        #[ink(message)]
+        pub fn allowances_of_owner(&self, owner: AccountId) -> Vec<(AccountId, u128)> {
+            self.spenders.get(owner).unwrap_or_default()
+                .into_iter()
+                .map(|spender| (spender, self.allowance(owner, spender)))
+                .collect()
+        }
+
+        /// Get how much spender can actually move from owner right now
+        ///
+        /// This is the smaller of the allowance and `spendable_balance(owner)`,
+        /// so it is 0 for expired allowances, a paused contract or a
+        /// blacklisted owner.
+        #[ink(message)]
+        pub fn spendable_allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
+            let (allowance, expiry) = self.allowances.get((owner, spender)).unwrap_or((0, NO_EXPIRY));
+            if self.env().block_number() > expiry {
+                return 0;
             }
+            allowance.min(self.spendable_balance(owner))
+        }
+
+        /// Get the last block an allowance can be used in (`None` if it never expires)
+        #[ink(message)]
+        pub fn allowance_expiry(&self, owner: AccountId, spender: AccountId) -> Option<BlockNumber> {
+            self.allowances.get((owner, spender))
+                .map(|(_, expiry)| expiry)
+                .filter(|expiry| *expiry != NO_EXPIRY)
+        }
+
+        /// Approve spender to spend tokens
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            self.set_allowance(caller, spender, amount, NO_EXPIRY);
+            self.env().emit_event(Approval {
+                owner: caller,
+                spender,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Approve spender, refusing to change one non-zero allowance into another
+        ///
+        /// Callers must approve 0 first, which closes the race where a spender
+        /// uses the old allowance just before the new one lands.
+        #[ink(message)]
+        pub fn safe_approve(&mut self, spender: AccountId, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            if amount != 0 && self.allowance(caller, spender) != 0 {
+                return Err(Error::UnsafeApproval);
+            }
+            self.approve(spender, amount)
+        }
+
+        /// Set several of the caller's allowances in one call
+        ///
+        /// Entries are applied in order, so a repeated spender ends up with its
+        /// last amount. Emits one `Approval` per entry.
+        #[ink(message)]
+        pub fn batch_approve(&mut self, approvals: Vec<(AccountId, u128)>) -> Result<()> {
+            let caller = self.env().caller();
+            self.ensure_batch_len(approvals.len())?;
+
+            for (spender, amount) in approvals {
+                if spender == caller {
+                    return Err(Error::SelfApproval);
+                }
+
+                self.set_allowance(caller, spender, amount, NO_EXPIRY);
+                self.env().emit_event(Approval {
+                    owner: caller,
+                    spender,
+                    amount,
+                });
+            }
+
+            Ok(())
+        }
+
+        /// Approve spender to spend tokens until `expiry_block` (inclusive)
+        #[ink(message)]
+        pub fn approve_with_expiry(
+            &mut self,
+            spender: AccountId,
+            amount: u128,
+            expiry_block: BlockNumber,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            self.set_allowance(caller, spender, amount, expiry_block);
+            self.env().emit_event(Approval {
+                owner: caller,
+                spender,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Revoke the caller's allowance for spender
+        ///
+        /// The storage entry is removed rather than set to zero, which frees
+        /// its storage deposit.
+        #[ink(message)]
+        pub fn revoke_allowance(&mut self, spender: AccountId) -> Result<()> {
+            let caller = self.env().caller();
+            self.set_allowance(caller, spender, 0, NO_EXPIRY);
+            self.env().emit_event(Approval {
+                owner: caller,
+                spender,
+                amount: 0,
+            });
+            Ok(())
+        }
+
+        /// Set an allowance from a signature by `owner`, submitted by anyone
+        ///
+        /// `signature` is `owner`'s sr25519 signature over the blake2x256 hash of
+        /// `(PERMIT_DOMAIN, contract, owner, spender, value, nonce, deadline)`, where
+        /// `nonce` is `permit_nonce(owner)`. Each permit can be used once and only
+        /// until `deadline` (milliseconds).
+        #[ink(message)]
+        pub fn permit(
+            &mut self,
+            owner: AccountId,
+            spender: AccountId,
+            value: u128,
+            deadline: Timestamp,
+            signature: [u8; 64],
+        ) -> Result<()> {
+            if self.env().block_timestamp() > deadline {
+                return Err(Error::PermitExpired);
+            }
+
+            let nonce = self.permit_nonce(owner);
+            let message = self.env().hash_encoded::<Blake2x256, _>(&(
+                PERMIT_DOMAIN,
+                self.env().account_id(),
+                owner,
+                spender,
+                value,
+                nonce,
+                deadline,
+            ));
+            self.env().sr25519_verify(&signature, &message, owner.as_ref())
+                .map_err(|_| Error::InvalidSignature)?;
+
+            // Consume the nonce so the permit cannot be replayed
+            self.permit_nonces.insert(owner, &nonce.checked_add(1).ok_or(Error::InvalidAmount)?);
+
+            self.set_allowance(owner, spender, value, NO_EXPIRY);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount: value,
+            });
+
+            Ok(())
+        }
+
+        /// Get the next approval permit nonce for an account
+        #[ink(message)]
+        pub fn permit_nonce(&self, owner: AccountId) -> u64 {
+            self.permit_nonces.get(owner).unwrap_or(0)
+        }
+
+        /// Transfer tokens using allowance
+        ///
+        /// An allowance of `u128::MAX` counts as infinite and is left unchanged.
+        /// Amounts above `confirm_threshold` are rejected; the owner of the funds
+        /// must stage them instead.
+        #[ink(message)]
+        pub fn transfer_from(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.non_reentrant(|this| this.transfer_from_unguarded(from, to, amount))
+        }
+
+        /// Move tokens between any two accounts without an allowance (owner only)
+        ///
+        /// Meant for recovering misdirected funds. Balance and overflow checks
+        /// still apply, but pause and blacklist do not.
+        #[ink(message)]
+        pub fn force_transfer(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            if from == to {
+                return Err(Error::TransferToSelf);
+            }
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            self.move_balance(from, to, amount)?;
+
+            self.env().emit_event(ForcedTransfer {
+                from,
+                to,
+                amount,
+                by: caller,
+            });
+
+            Ok(())
+        }
+
+        /// Withdraw tokens held at the contract's own address (owner only)
+        ///
+        /// Meant for recovering stuck fees or escrow. Works while paused.
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            let contract = self.env().account_id();
+            if contract == to {
+                return Err(Error::TransferToSelf);
+            }
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            self.move_balance(contract, to, amount)?;
+
+            self.env().emit_event(EmergencyWithdrawal { to, amount });
+
+            Ok(())
+        }
+
+        /// Move dust balances from many accounts into `to` (owner only)
+        ///
+        /// Each listed account whose whole balance is below `threshold` and
+        /// movable, and who has approved the caller for at least that balance,
+        /// has its full balance moved to `to`. Accounts a transfer to `to` would
+        /// be rejected for (paused, blacklisted, invalid recipient) are skipped
+        /// like any other. Swept transfers pay the transfer fee and are recorded
+        /// in the history. Returns the total amount taken from the accounts.
+        #[ink(message)]
+        pub fn sweep_dust(&mut self, accounts: Vec<AccountId>, threshold: u128, to: AccountId) -> Result<u128> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            self.ensure_batch_len(accounts.len())?;
+
+            let now = self.env().block_number();
+            let mut total_swept: u128 = 0;
+            for account in accounts {
+                let balance = self.balance_of(account);
+                if account == to || balance == 0 || balance >= threshold || self.available_balance(account) < balance {
+                    continue;
+                }
+
+                if self.ensure_transfer_allowed(account, to, balance).is_err() {
+                    continue;
+                }
+
+                let (allowance, expiry) = self.allowances.get((account, caller)).unwrap_or((0, NO_EXPIRY));
+                if now > expiry || allowance < balance {
+                    continue;
+                }
+
+                self.transfer_with_fee(account, to, balance)?;
+                if allowance != u128::MAX {
+                    self.set_allowance(account, caller, allowance.saturating_sub(balance), expiry);
+                }
+                total_swept = total_swept.saturating_add(balance);
+            }
+
+            Ok(total_swept)
+        }
+
+        /// Pause all transfers (owner only)
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.paused = true;
+            self.env().emit_event(Paused { by: caller });
+            Ok(())
+        }
+
+        /// Unpause all transfers (owner only)
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.paused = false;
+            self.env().emit_event(Unpaused { by: caller });
+            Ok(())
+        }
+
+        /// Let an account keep moving funds while the contract is paused (owner only)
+        #[ink(message)]
+        pub fn add_pause_exempt(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.pause_exempt.insert(account, &true);
+            Ok(())
+        }
+
+        /// Remove an account's pause exemption (owner only)
+        #[ink(message)]
+        pub fn remove_pause_exempt(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.pause_exempt.remove(account);
+            Ok(())
+        }
+
+        /// Check if an account can move funds while the contract is paused
+        #[ink(message)]
+        pub fn is_pause_exempt(&self, account: AccountId) -> bool {
+            self.pause_exempt.get(account).unwrap_or(false)
+        }
+
+        /// Set the blocks an account must wait between transfers (owner only, 0 disables)
+        ///
+        /// Applies to the sending account of every transfer, including batch and
+        /// confirmed staged transfers. The owner and pause-exempt accounts are
+        /// never throttled.
+        #[ink(message)]
+        pub fn set_transfer_cooldown_blocks(&mut self, blocks: BlockNumber) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.transfer_cooldown_blocks = blocks;
+            Ok(())
+        }
+
+        /// Get the blocks an account must wait between transfers
+        #[ink(message)]
+        pub fn transfer_cooldown_blocks(&self) -> BlockNumber {
+            self.transfer_cooldown_blocks
+        }
+
+        /// Set the per-block outflow that auto-pauses transfers (owner only)
+        ///
+        /// Once the amount moved by transfers of any kind (including batch
+        /// and confirmed staged transfers) within one block exceeds the
+        /// threshold, the contract pauses itself. The transfer that crosses
+        /// the threshold still goes through. `None` disables this.
+        #[ink(message)]
+        pub fn set_outflow_threshold(&mut self, threshold: Option<u128>) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.outflow_threshold = threshold;
+            Ok(())
+        }
+
+        /// Get the per-block outflow that auto-pauses transfers
+        #[ink(message)]
+        pub fn outflow_threshold(&self) -> Option<u128> {
+            self.outflow_threshold
+        }
+
+        /// Send a foreign PSP22 token held by this contract to `to` (owner only)
+        ///
+        /// Recovers tokens accidentally transferred to the contract's address
+        /// by calling `PSP22::transfer` on the `token` contract.
+        #[ink(message)]
+        pub fn recover_token(&mut self, token: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            if token == self.env().account_id() {
+                return Err(Error::CannotRecoverOwnToken);
+            }
+
+            let mut foreign: ink::contract_ref!(PSP22) = token.into();
+            match foreign.call_mut().transfer(to, amount, Vec::new()).try_invoke() {
+                Ok(Ok(Ok(()))) => {}
+                _ => return Err(Error::RecoveryFailed),
+            }
+
+            self.env().emit_event(TokenRecovered { token, to, amount });
+
+            Ok(())
+        }
+
+        /// Check if contract is paused
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Set whether burns stay allowed while the contract is paused (owner only)
+        #[ink(message)]
+        pub fn set_burn_while_paused(&mut self, allowed: bool) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.burn_while_paused = allowed;
+            Ok(())
+        }
+
+        /// Check if burns stay allowed while the contract is paused
+        #[ink(message)]
+        pub fn burn_while_paused(&self) -> bool {
+            self.burn_while_paused
+        }
+
+        /// Blacklist an address (owner or blacklist manager)
+        #[ink(message)]
+        pub fn blacklist(&mut self, account: AccountId) -> Result<()> {
+            if !self.can_manage_blacklist(self.env().caller()) {
+                return Err(Error::NotOwner);
+            }
+            if account == self.owner {
+                return Err(Error::CannotBlacklistOwner);
+            }
+            self.blacklisted.insert(account, &true);
+            Ok(())
+        }
+
+        /// Remove from blacklist (owner or blacklist manager)
+        #[ink(message)]
+        pub fn unblacklist(&mut self, account: AccountId) -> Result<()> {
+            if !self.can_manage_blacklist(self.env().caller()) {
+                return Err(Error::NotOwner);
+            }
+            self.blacklisted.insert(account, &false);
+            Ok(())
+        }
+
+        /// Allow an account to blacklist and unblacklist addresses (owner only)
+        #[ink(message)]
+        pub fn add_blacklist_manager(&mut self, manager: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.blacklist_managers.insert(manager, &true);
+            self.env().emit_event(BlacklistManagerAdded { manager });
+            Ok(())
+        }
+
+        /// Revoke an account's blacklist manager role (owner only)
+        #[ink(message)]
+        pub fn remove_blacklist_manager(&mut self, manager: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.blacklist_managers.remove(manager);
+            self.env().emit_event(BlacklistManagerRemoved { manager });
+            Ok(())
+        }
+
+        /// Check if an account is a blacklist manager
+        #[ink(message)]
+        pub fn is_blacklist_manager(&self, account: AccountId) -> bool {
+            self.blacklist_managers.get(account).unwrap_or(false)
+        }
+
+        /// Check if address is blacklisted
+        #[ink(message)]
+        pub fn is_blacklisted(&self, account: AccountId) -> bool {
+            self.blacklisted.get(account).unwrap_or(false)
+        }
+
+        /// Check the blacklist status of several accounts, in the given order
+        #[ink(message)]
+        pub fn are_blacklisted(&self, accounts: Vec<AccountId>) -> Vec<bool> {
+            accounts.into_iter()
+                .map(|account| self.is_blacklisted(account))
+                .collect()
+        }
+
+        /// Batch transfer to multiple addresses
+        ///
+        /// Every leg gets the same pause, blacklist, recipient and amount checks
+        /// as `transfer`, and the whole batch fails if any leg is invalid,
+        /// including amounts above `confirm_threshold`.
+        #[ink(message)]
+        pub fn batch_transfer(&mut self, recipients: Vec<(AccountId, u128)>) -> Result<()> {
+            let caller = self.env().caller();
+            self.non_reentrant(|this| this.batch_transfer_unguarded(caller, recipients))
+        }
+
+        /// Batch transfer from `from`, authorized by its signature and submitted by anyone
+        ///
+        /// `signature` is `from`'s sr25519 signature over the blake2x256 hash of
+        /// `(BATCH_TRANSFER_DOMAIN, contract, from, recipients, nonce)`. `nonce`
+        /// must be at least `batch_nonce(from)`; using it raises the next
+        /// accepted nonce to `nonce + 1`, so a batch cannot be replayed.
+        #[ink(message)]
+        pub fn batch_transfer_signed(
+            &mut self,
+            from: AccountId,
+            recipients: Vec<(AccountId, u128)>,
+            nonce: u64,
+            signature: [u8; 64],
+        ) -> Result<()> {
+            if nonce < self.batch_nonce(from) {
+                return Err(Error::NonceReused);
+            }
+
+            let message = self.env().hash_encoded::<Blake2x256, _>(&(
+                BATCH_TRANSFER_DOMAIN,
+                self.env().account_id(),
+                from,
+                &recipients,
+                nonce,
+            ));
+            self.env().sr25519_verify(&signature, &message, from.as_ref())
+                .map_err(|_| Error::InvalidSignature)?;
+
+            // Consume the nonce so the batch cannot be replayed
+            self.batch_nonces.insert(from, &nonce.checked_add(1).ok_or(Error::InvalidAmount)?);
+
+            self.non_reentrant(|this| this.batch_transfer_unguarded(from, recipients))
+        }
+
+        /// Get the lowest nonce `batch_transfer_signed` accepts for an account
+        #[ink(message)]
+        pub fn batch_nonce(&self, account: AccountId) -> u64 {
+            self.batch_nonces.get(account).unwrap_or(0)
+        }
+
+        /// Batch transfer where each leg succeeds or fails on its own
+        ///
+        /// Legs that a plain `transfer` would reject (while paused, to or from a
+        /// blacklisted account, to self or the zero account, zero or stageable
+        /// amounts) and legs the caller can no longer cover are skipped instead
+        /// of failing the whole call. Returns one flag per
+        /// recipient, in order, telling whether that leg was executed. Every leg
+        /// reads and writes balances and emits its own event, so this costs more
+        /// gas than the atomic `batch_transfer`, and skipped legs still pay for
+        /// their checks.
+        #[ink(message)]
+        pub fn batch_transfer_lenient(&mut self, recipients: Vec<(AccountId, u128)>) -> Result<Vec<bool>> {
+            self.non_reentrant(|this| {
+                let caller = this.env().caller();
+                this.ensure_batch_len(recipients.len())?;
+                this.check_transfer_cooldown(caller)?;
+
+                let mut moved: u128 = 0;
+                let results = recipients.into_iter()
+                    .map(|(to, amount)| {
+                        let executed = this.ensure_transfer_allowed(caller, to, amount).is_ok()
+                            && this.ensure_below_confirm_threshold(amount).is_ok()
+                            && this.transfer_with_fee(caller, to, amount).is_ok();
+                        if executed {
+                            moved = moved.saturating_add(amount);
+                        }
+                        executed
+                    })
+                    .collect();
+
+                if moved > 0 {
+                    this.enforce_transfer_cooldown(caller)?;
+                }
+                this.record_outflow(moved);
+
+                Ok(results)
+            })
+        }
+
+        /// Set the transfer fee in basis points (owner only, at most 10000)
+        ///
+        /// Charged on `transfer`, `transfer_from`, every batch transfer leg and
+        /// confirmed staged transfers.
+        #[ink(message)]
+        pub fn set_fee_bps(&mut self, fee_bps: u16) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if fee_bps > MAX_BPS {
+                return Err(Error::InvalidFee);
+            }
+            self.fee_bps = fee_bps;
+            Ok(())
+        }
+
+        /// Get the transfer fee in basis points
+        #[ink(message)]
+        pub fn fee_bps(&self) -> u16 {
+            self.fee_bps
+        }
+
+        /// Set the account credited with transfer fees (owner only)
+        #[ink(message)]
+        pub fn set_fee_recipient(&mut self, fee_recipient: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.fee_recipient = fee_recipient;
+            Ok(())
+        }
+
+        /// Get the account credited with transfer fees
+        #[ink(message)]
+        pub fn fee_recipient(&self) -> AccountId {
+            self.fee_recipient
+        }
+
+        /// Exempt an account from the transfer fee, as sender or recipient (owner only)
+        #[ink(message)]
+        pub fn add_fee_exempt(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.fee_exempt.insert(account, &true);
+            Ok(())
+        }
+
+        /// Remove an account's transfer fee exemption (owner only)
+        #[ink(message)]
+        pub fn remove_fee_exempt(&mut self, account: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.fee_exempt.remove(account);
+            Ok(())
+        }
+
+        /// Check if transfers from or to an account skip the transfer fee
+        #[ink(message)]
+        pub fn is_fee_exempt(&self, account: AccountId) -> bool {
+            self.fee_exempt.get(account).unwrap_or(false)
+        }
+
+        /// Set the share of each burn sent to the treasury in basis points (owner only, at most 10000)
+        #[ink(message)]
+        pub fn set_burn_fee_bps(&mut self, burn_fee_bps: u16) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            if burn_fee_bps > MAX_BPS {
+                return Err(Error::InvalidFee);
+            }
+            self.burn_fee_bps = burn_fee_bps;
+            Ok(())
+        }
+
+        /// Get the share of each burn sent to the treasury in basis points
+        #[ink(message)]
+        pub fn burn_fee_bps(&self) -> u16 {
+            self.burn_fee_bps
+        }
+
+        /// Set the account credited with burn fees (owner only)
+        #[ink(message)]
+        pub fn set_treasury(&mut self, treasury: AccountId) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.treasury = treasury;
+            Ok(())
+        }
+
+        /// Get the account credited with burn fees
+        #[ink(message)]
+        pub fn treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        /// Set the maximum number of entries in a batch call (owner only)
+        #[ink(message)]
+        pub fn set_max_batch_len(&mut self, max_len: u32) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.max_batch_len = max_len;
+            Ok(())
+        }
+
+        /// Get the maximum number of entries in a batch call
+        #[ink(message)]
+        pub fn max_batch_len(&self) -> u32 {
+            self.max_batch_len
+        }
+
+        /// Set the amount above which transfers must be staged (owner only, 0 disables)
+        #[ink(message)]
+        pub fn set_confirm_threshold(&mut self, threshold: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.confirm_threshold = threshold;
+            Ok(())
+        }
+
+        /// Get the amount above which transfers must be staged
+        #[ink(message)]
+        pub fn confirm_threshold(&self) -> u128 {
+            self.confirm_threshold
+        }
+
+        /// Set how many blocks a staged transfer stays confirmable (owner only)
+        #[ink(message)]
+        pub fn set_confirm_window(&mut self, blocks: BlockNumber) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            self.confirm_window = blocks;
+            Ok(())
+        }
+
+        /// Get how many blocks a staged transfer stays confirmable
+        #[ink(message)]
+        pub fn confirm_window(&self) -> BlockNumber {
+            self.confirm_window
+        }
+
+        /// Stage a transfer that must be confirmed with `confirm_transfer`
+        ///
+        /// The amount is locked in the caller's account until the transfer is
+        /// confirmed or cancelled. Returns the id of the staged transfer.
+        #[ink(message)]
+        pub fn stage_transfer(&mut self, to: AccountId, amount: u128) -> Result<u32> {
+            let caller = self.env().caller();
+            self.ensure_transfer_allowed(caller, to, amount)?;
+
+            if self.available_balance(caller) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let new_staged = self.staged.get(caller).unwrap_or(0).checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            let id = self.next_transfer_id;
+            self.next_transfer_id = id.checked_add(1).ok_or(Error::InvalidAmount)?;
+            let expires_at = self.env().block_number().saturating_add(self.confirm_window);
+
+            self.staged.insert(caller, &new_staged);
+            self.pending_transfers.insert(id, &PendingTransfer {
+                from: caller,
+                to,
+                amount,
+                expires_at,
+            });
+
+            self.env().emit_event(TransferStaged {
+                id,
+                from: caller,
+                to,
+                amount,
+                expires_at,
+            });
+
+            Ok(id)
+        }
+
+        /// Confirm and execute a staged transfer (sender only)
+        #[ink(message)]
+        pub fn confirm_transfer(&mut self, id: u32) -> Result<()> {
+            let pending = self.take_pending_transfer(id)?;
+
+            if self.env().block_number() > pending.expires_at {
+                return Err(Error::ConfirmationExpired);
+            }
+
+            // Guards are re-checked since pause or blacklist may have changed
+            self.ensure_transfer_allowed(pending.from, pending.to, pending.amount)?;
+            self.enforce_transfer_cooldown(pending.from)?;
+            self.transfer_with_fee(pending.from, pending.to, pending.amount)?;
+            self.record_outflow(pending.amount);
+
+            Ok(())
+        }
+
+        /// Cancel a staged transfer and unlock its funds (sender only)
+        ///
+        /// Expired transfers can still be cancelled to release the locked amount.
+        #[ink(message)]
+        pub fn cancel_transfer(&mut self, id: u32) -> Result<()> {
+            let pending = self.take_pending_transfer(id)?;
+
+            self.env().emit_event(TransferCancelled {
+                id,
+                from: pending.from,
+            });
+
+            Ok(())
+        }
+
+        /// Get a staged transfer by id
+        #[ink(message)]
+        pub fn pending_transfer(&self, id: u32) -> Option<PendingTransfer> {
+            self.pending_transfers.get(id)
+        }
+
+        /// Get the amount an account has locked in staged transfers
+        #[ink(message)]
+        pub fn staged_balance(&self, account: AccountId) -> u128 {
+            self.staged.get(account).unwrap_or(0)
+        }
+
+        /// Move tokens from the owner to a beneficiary under a vesting schedule (owner only)
+        ///
+        /// The tokens count toward the beneficiary's balance immediately but
+        /// only become spendable as they are released with `release_vested`.
+        #[ink(message)]
+        pub fn create_vesting(
+            &mut self,
+            beneficiary: AccountId,
+            total: u128,
+            duration_blocks: BlockNumber,
+        ) -> Result<()> {
+            let caller = self.env().caller();
+            if caller != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            if total == 0 || duration_blocks == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            if let Some(schedule) = self.vesting.get(beneficiary) {
+                if schedule.released < schedule.total {
+                    return Err(Error::VestingExists);
+                }
+            }
+
+            if caller == beneficiary {
+                return Err(Error::TransferToSelf);
+            }
+
+            self.transfer_from_to(caller, beneficiary, total)?;
+
+            let start_block = self.env().block_number();
+            self.vesting.insert(beneficiary, &VestingSchedule {
+                total,
+                released: 0,
+                start_block,
+                duration_blocks,
+            });
+
+            self.env().emit_event(VestingCreated {
+                beneficiary,
+                total,
+                start_block,
+                duration_blocks,
+            });
+
+            Ok(())
+        }
+
+        /// Release the caller's vested but not yet released tokens
+        #[ink(message)]
+        pub fn release_vested(&mut self) -> Result<u128> {
+            let caller = self.env().caller();
+            let mut schedule = self.vesting.get(caller).ok_or(Error::NoVestingSchedule)?;
+
+            let releasable = schedule.vested_at(self.env().block_number())
+                .saturating_sub(schedule.released);
+            if releasable == 0 {
+                return Err(Error::NothingToRelease);
+            }
+
+            schedule.released = schedule.released.saturating_add(releasable);
+            self.vesting.insert(caller, &schedule);
+
+            self.env().emit_event(VestingReleased {
+                beneficiary: caller,
+                amount: releasable,
+            });
+
+            Ok(releasable)
+        }
+
+        /// Get the amount vested so far for an account, including released tokens
+        #[ink(message)]
+        pub fn vested_amount(&self, account: AccountId) -> u128 {
+            self.vesting.get(account)
+                .map(|schedule| schedule.vested_at(self.env().block_number()))
+                .unwrap_or(0)
+        }
+
+        /// Get the vesting schedule of an account
+        #[ink(message)]
+        pub fn vesting_schedule(&self, account: AccountId) -> Option<VestingSchedule> {
+            self.vesting.get(account)
+        }
+
+        /// Lock part of an account's balance so it cannot be moved (owner only)
+        ///
+        /// Locked tokens still count toward the account's balance.
+        #[ink(message)]
+        pub fn lock(&mut self, account: AccountId, amount: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            if self.available_balance(account) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let new_locked = self.locked_balance(account).checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            self.locked_balances.insert(account, &new_locked);
+
+            Ok(())
+        }
+
+        /// Unlock part of an account's locked balance (owner only)
+        #[ink(message)]
+        pub fn unlock(&mut self, account: AccountId, amount: u128) -> Result<()> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+
+            let new_locked = self.locked_balance(account).checked_sub(amount)
+                .ok_or(Error::InvalidAmount)?;
+            if new_locked == 0 {
+                self.locked_balances.remove(account);
+            } else {
+                self.locked_balances.insert(account, &new_locked);
+            }
+
+            Ok(())
+        }
+
+        /// Get the amount of an account's balance locked by the owner
+        #[ink(message)]
+        pub fn locked_balance(&self, account: AccountId) -> u128 {
+            self.locked_balances.get(account).unwrap_or(0)
+        }
+
+        /// Get how much of an account's balance can be moved right now
+        ///
+        /// This is the same figure the transfer checks use: the balance minus
+        /// everything reported by `encumbrances`. Returns 0 while the contract
+        /// is paused or the account is blacklisted.
+        #[ink(message)]
+        pub fn spendable_balance(&self, account: AccountId) -> u128 {
+            if (self.paused && !self.is_pause_exempt(account)) || self.is_blacklisted(account) {
+                return 0;
+            }
+            self.available_balance(account)
+        }
+
+        /// Get the breakdown of an account's encumbered balance
+        #[ink(message)]
+        pub fn encumbrances(&self, account: AccountId) -> Encumbrances {
+            let unvested = self.vesting.get(account)
+                .map(|schedule| schedule.total.saturating_sub(schedule.released))
+                .unwrap_or(0);
+
+            Encumbrances {
+                staged: self.staged_balance(account),
+                unvested,
+                locked: self.locked_balance(account),
+            }
+        }
+
+        /// Balance minus every encumbrance
+        fn available_balance(&self, account: AccountId) -> u128 {
+            self.balance_of(account).saturating_sub(self.encumbrances(account).total())
+        }
+
+        /// Run `f` with the reentrancy guard held, rejecting nested entry
+        fn non_reentrant<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+            if self.locked {
+                return Err(Error::ReentrantCall);
+            }
+
+            self.locked = true;
+            let result = f(self);
+            self.locked = false;
+
+            result
+        }
+
+        /// Body of `transfer`, run under the reentrancy guard
+        ///
+        /// Returns the amount credited to `to`.
+        fn transfer_unguarded(&mut self, to: AccountId, amount: u128) -> Result<u128> {
+            let caller = self.env().caller();
+            self.check_transfer(caller, to, amount)?;
+            self.enforce_transfer_cooldown(caller)?;
+
+            let received = self.transfer_with_fee(caller, to, amount)?;
+
+            self.record_outflow(amount);
+
+            Ok(received)
+        }
+
+        /// Body of `burn`, run under the reentrancy guard
+        fn burn_unguarded(&mut self, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            let caller_balance = self.balances.get(caller).unwrap_or(0);
+
+            self.ensure_burn_not_paused(caller)?;
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            if self.available_balance(caller) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let new_balance = caller_balance.checked_sub(amount)
+                .ok_or(Error::InsufficientBalance)?;
+
+            self.sub_supply(amount)?;
+            self.set_balance(caller, new_balance);
+            self.touch_activity(caller);
+
+            self.env().emit_event(TokensBurned {
+                from: caller,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Body of `burn`: send the burn fee to the treasury and burn the rest
+        ///
+        /// Returns the amount removed from the supply.
+        fn burn_with_fee(&mut self, amount: u128) -> Result<u128> {
+            let caller = self.env().caller();
+
+            self.ensure_burn_not_paused(caller)?;
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            if self.available_balance(caller) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            // The fee rounds down, so dust amounts are burned in full
+            let fee = if caller == self.treasury {
+                0
+            } else {
+                Self::bps_of(amount, self.burn_fee_bps)
+            };
+
+            if fee > 0 {
+                self.transfer_from_to(caller, self.treasury, fee)?;
+            }
+
+            let burned = amount.saturating_sub(fee);
+            if burned > 0 {
+                self.burn_unguarded(burned)?;
+            }
+
+            Ok(burned)
+        }
+
+        /// Body of `transfer_from`, run under the reentrancy guard
+        fn transfer_from_unguarded(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            let caller = self.env().caller();
+            let (allowance, expiry) = self.allowances.get((from, caller)).unwrap_or((0, NO_EXPIRY));
+
+            self.ensure_not_paused(from, caller)?;
+            self.ensure_not_blacklisted(from, to)?;
+
+            // Check if transferring to self, before any allowance is used
+            if from == to {
+                return Err(Error::TransferToSelf);
+            }
+
+            Self::ensure_valid_recipient(to)?;
+
+            self.enforce_transfer_cooldown(from)?;
+
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            self.ensure_below_confirm_threshold(amount)?;
+
+            if self.env().block_number() > expiry {
+                return Err(Error::AllowanceExpired);
+            }
+
+            if allowance < amount {
+                return Err(Error::InsufficientAllowance);
+            }
+
+            self.transfer_with_fee(from, to, amount)?;
+
+            // An allowance of u128::MAX is treated as infinite and never decremented
+            if allowance != u128::MAX {
+                let new_allowance = allowance.checked_sub(amount)
+                    .ok_or(Error::InsufficientAllowance)?;
+                self.set_allowance(from, caller, new_allowance, expiry);
+            }
+
+            self.record_outflow(amount);
+
+            Ok(())
+        }
+
+        /// Body of `batch_transfer`, run under the reentrancy guard
+        fn batch_transfer_unguarded(&mut self, from: AccountId, recipients: Vec<(AccountId, u128)>) -> Result<()> {
+            self.ensure_batch_len(recipients.len())?;
+            self.enforce_transfer_cooldown(from)?;
+
+            // Check if sender has enough balance for all transfers
+            let mut total_amount: u128 = 0;
+            for (_, amount) in &recipients {
+                total_amount = total_amount.checked_add(*amount)
+                    .ok_or(Error::InvalidAmount)?;
+            }
+            if self.available_balance(from) < total_amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            // Every leg must pass the same checks as a single transfer
+            for (to, amount) in &recipients {
+                self.ensure_transfer_allowed(from, *to, *amount)?;
+                self.ensure_below_confirm_threshold(*amount)?;
+            }
+
+            // Perform all transfers
+            for (to, amount) in recipients {
+                self.transfer_with_fee(from, to, amount)?;
+            }
+
+            self.record_outflow(total_amount);
+
+            Ok(())
+        }
+
+        /// Reject the sender if it transferred within the cooldown
+        fn check_transfer_cooldown(&self, sender: AccountId) -> Result<()> {
+            if self.transfer_cooldown_blocks == 0 || sender == self.owner || self.is_pause_exempt(sender) {
+                return Ok(());
+            }
+
+            if let Some(last) = self.last_transfer.get(sender) {
+                if self.env().block_number() < last.saturating_add(self.transfer_cooldown_blocks) {
+                    return Err(Error::TransferCooldown);
+                }
+            }
+
+            Ok(())
+        }
+
+        /// Check the sender's cooldown, then record this transfer
+        fn enforce_transfer_cooldown(&mut self, sender: AccountId) -> Result<()> {
+            self.check_transfer_cooldown(sender)?;
+
+            if self.transfer_cooldown_blocks > 0 {
+                self.last_transfer.insert(sender, &self.env().block_number());
+            }
+
+            Ok(())
+        }
+
+        /// Append a transfer to both parties' histories, dropping their oldest entries
+        ///
+        /// The sender's record shows the `sent` amount debited and the recipient's
+        /// the `received` amount credited, so fees show up as the difference. A
+        /// recipient credited nothing gets no record.
+        fn record_transfer(&mut self, from: AccountId, to: AccountId, sent: u128, received: u128) {
+            let timestamp = self.env().block_timestamp();
+            for (account, counterparty, amount, incoming) in [(from, to, sent, false), (to, from, received, true)] {
+                if amount == 0 {
+                    continue;
+                }
+
+                let mut history = self.transfer_history(account);
+                if history.len() >= MAX_TRANSFER_HISTORY {
+                    history.remove(0);
+                }
+                history.push(TransferRecord {
+                    counterparty,
+                    amount,
+                    timestamp,
+                    incoming,
+                });
+                self.recent_transfers.insert(account, &history);
+                self.touch_activity(account);
+            }
+        }
+
+        /// Mark `account` as active in the current block
+        fn touch_activity(&mut self, account: AccountId) {
+            self.last_activity.insert(account, &self.env().block_timestamp());
+        }
+
+        /// Add to this block's outflow and trip the circuit breaker if needed
+        fn record_outflow(&mut self, amount: u128) {
+            let block = self.env().block_number();
+            if block != self.current_block {
+                self.current_block = block;
+                self.block_outflow = 0;
+            }
+            self.block_outflow = self.block_outflow.saturating_add(amount);
+
+            if let Some(threshold) = self.outflow_threshold {
+                if !self.paused && self.block_outflow > threshold {
+                    self.paused = true;
+                    self.env().emit_event(Paused { by: self.env().account_id() });
+                }
+            }
+        }
+
+        /// Mint tokens to an account, updating the supply and emitting the mint event
+        fn mint_to(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            self.add_supply(amount)?;
+            self.credit_minted(to, amount)
+        }
+
+        /// Add minted tokens to `total_supply` and `total_minted`
+        ///
+        /// Batch mints call this once with their aggregate amount.
+        fn add_supply(&mut self, amount: u128) -> Result<()> {
+            let new_supply = self.total_supply.checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+            let new_total_minted = self.total_minted.checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+
+            self.total_supply = new_supply;
+            self.total_minted = new_total_minted;
+
+            Ok(())
+        }
+
+        /// Remove burned tokens from `total_supply` and add them to `total_burned`
+        fn sub_supply(&mut self, amount: u128) -> Result<()> {
+            let new_supply = self.total_supply.checked_sub(amount)
+                .ok_or(Error::SupplyUnderflow)?;
+            let new_total_burned = self.total_burned.checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+
+            self.total_supply = new_supply;
+            self.total_burned = new_total_burned;
+
+            Ok(())
+        }
+
+        /// Credit newly minted tokens to an account and emit the mint event
+        ///
+        /// The supply must already have been updated with `add_supply`.
+        fn credit_minted(&mut self, to: AccountId, amount: u128) -> Result<()> {
+            // Every minting path goes through here, so this covers them all
+            if self.minting_finished {
+                return Err(Error::MintingFinished);
+            }
+
+            if self.is_blacklisted(to) {
+                return Err(Error::Blacklisted);
+            }
+
+            Self::ensure_valid_recipient(to)?;
+
+            // Check for overflow
+            let current_balance = self.balances.get(to).unwrap_or(0);
+            let new_balance = current_balance.checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+
+            self.set_balance(to, new_balance);
+            self.touch_activity(to);
+
+            // Emit event
+            self.env().emit_event(TokensMinted {
+                to,
+                amount,
+                minter: self.env().caller(),
+            });
+
+            Ok(())
+        }
+
+        /// Whether `account` is the owner or a blacklist manager
+        fn can_manage_blacklist(&self, account: AccountId) -> bool {
+            account == self.owner || self.is_blacklist_manager(account)
+        }
+
+        /// Reject single-key minting once mints need a co-owner quorum
+        fn ensure_single_owner_minting(&self) -> Result<()> {
+            if self.mint_threshold > 1 {
+                return Err(Error::QuorumRequired);
+            }
+            Ok(())
+        }
+
+        /// Reject minting calls creating more than `max_mint_per_tx` in total
+        fn ensure_mint_limit(&self, amount: u128) -> Result<()> {
+            match self.max_mint_per_tx {
+                Some(limit) if amount > limit => Err(Error::MintLimitExceeded),
+                _ => Ok(()),
+            }
+        }
+
+        /// Reject empty batches and batches longer than `max_batch_len`
+        fn ensure_batch_len(&self, len: usize) -> Result<()> {
+            if len == 0 {
+                return Err(Error::EmptyBatch);
+            }
+            if len > self.max_batch_len as usize {
+                return Err(Error::BatchTooLarge);
+            }
+            Ok(())
+        }
+
+        /// Reject transfers while paused unless `from` or the operator moving its funds is pause-exempt
+        fn ensure_not_paused(&self, from: AccountId, operator: AccountId) -> Result<()> {
+            if self.paused && !self.is_pause_exempt(operator) && !self.is_pause_exempt(from) {
+                return Err(Error::InvalidAmount); // Using InvalidAmount as pause error
+            }
+            Ok(())
+        }
+
+        /// Reject burns while paused unless `burn_while_paused` is set or `from` is exempt
+        fn ensure_burn_not_paused(&self, from: AccountId) -> Result<()> {
+            if self.paused && !self.burn_while_paused && !self.is_pause_exempt(from) {
+                return Err(Error::ContractPaused);
+            }
+            Ok(())
+        }
+
+        /// Reject transfers from or to a blacklisted account
+        fn ensure_not_blacklisted(&self, from: AccountId, to: AccountId) -> Result<()> {
+            if self.blacklisted.get(from).unwrap_or(false) || self.blacklisted.get(to).unwrap_or(false) {
+                return Err(Error::InvalidAmount); // Using InvalidAmount as blacklist error
+            }
+            Ok(())
+        }
+
+        /// Check the pause, blacklist, self-transfer and amount rules for a transfer
+        fn ensure_transfer_allowed(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            //--- ASSIGNMENT --- Check pause state and blacklist ---//
+            self.ensure_not_paused(from, from)?;
+            self.ensure_not_blacklisted(from, to)?;
+
+            // Check if transferring to self
+            if from == to {
+                return Err(Error::TransferToSelf);
+            }
+
+            Self::ensure_valid_recipient(to)?;
+
+            // Check for valid amount
+            if amount == 0 {
+                return Err(Error::InvalidAmount);
+            }
+
+            Ok(())
+        }
+
+        /// Reject amounts above `confirm_threshold`, which must be staged and confirmed
+        fn ensure_below_confirm_threshold(&self, amount: u128) -> Result<()> {
+            if self.confirm_threshold > 0 && amount > self.confirm_threshold {
+                return Err(Error::ConfirmationRequired);
+            }
+            Ok(())
+        }
+
+        /// Reject the default (all-zero) account, usually an unset address in a front end
+        fn ensure_valid_recipient(to: AccountId) -> Result<()> {
+            if to == AccountId::from([0u8; 32]) {
+                return Err(Error::InvalidRecipient);
+            }
+            Ok(())
+        }
+
+        /// Run every check `transfer` makes without changing any state
+        fn check_transfer(&self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.ensure_transfer_allowed(from, to, amount)?;
+
+            self.ensure_below_confirm_threshold(amount)?;
+
+            self.check_transfer_cooldown(from)?;
+
+            if self.available_balance(from) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            Ok(())
+        }
+
+        /// Move `amount` from `from` to `to`, sending the transfer fee to `fee_recipient`
+        ///
+        /// Every caller-initiated transfer goes through here so the fee and the
+        /// history and activity bookkeeping cannot be skipped. Nothing is moved
+        /// unless the whole amount is available. Returns the amount credited to `to`.
+        fn transfer_with_fee(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<u128> {
+            if self.available_balance(from) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            let fee = self.transfer_fee(from, to, amount);
+            let received = amount.saturating_sub(fee);
+            if received > 0 {
+                self.transfer_from_to(from, to, received)?;
+            }
+            self.record_transfer(from, to, amount, received);
+
+            if fee > 0 {
+                let fee_recipient = self.fee_recipient;
+                self.move_balance(from, fee_recipient, fee)?;
+                self.env().emit_event(FeeCollected {
+                    from,
+                    to: fee_recipient,
+                    amount: fee,
+                });
+            }
+
+            Ok(received)
+        }
+
+        /// Fee charged on a transfer of `amount` from `from` to `to`
+        fn transfer_fee(&self, from: AccountId, to: AccountId, amount: u128) -> u128 {
+            // The fee recipient sending tokens would only pay itself
+            if from == self.fee_recipient || self.is_fee_exempt(from) || self.is_fee_exempt(to) {
+                return 0;
+            }
+            Self::bps_of(amount, self.fee_bps)
+        }
+
+        /// Move tokens between accounts and emit the transfer event
+        fn transfer_from_to(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            self.move_balance(from, to, amount)?;
+
+            // Emit event
+            self.env().emit_event(TokensTransferred {
+                from,
+                to,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Store an allowance and keep the owner's spender list in sync
+        ///
+        /// A zero amount removes the entry, freeing its storage deposit.
+        fn set_allowance(&mut self, owner: AccountId, spender: AccountId, amount: u128, expiry: BlockNumber) {
+            let mut spenders = self.spenders.get(owner).unwrap_or_default();
+            let listed = spenders.contains(&spender);
+
+            if amount == 0 {
+                self.allowances.remove((owner, spender));
+                if listed {
+                    spenders.retain(|listed_spender| *listed_spender != spender);
+                    if spenders.is_empty() {
+                        self.spenders.remove(owner);
+                    } else {
+                        self.spenders.insert(owner, &spenders);
+                    }
+                }
+            } else {
+                self.allowances.insert((owner, spender), &(amount, expiry));
+                if !listed {
+                    spenders.push(spender);
+                    self.spenders.insert(owner, &spenders);
+                }
+            }
+        }
+
+        /// Move tokens between two different accounts without emitting an event
+        fn move_balance(&mut self, from: AccountId, to: AccountId, amount: u128) -> Result<()> {
+            // Get current balances
+            let from_balance = self.balances.get(from).unwrap_or(0);
+            let to_balance = self.balances.get(to).unwrap_or(0);
+
+            // Check if sender has sufficient unencumbered balance
+            if self.available_balance(from) < amount {
+                return Err(Error::InsufficientBalance);
+            }
+
+            // Calculate new balances
+            let new_from_balance = from_balance.checked_sub(amount)
+                .ok_or(Error::InsufficientBalance)?;
+            let new_to_balance = to_balance.checked_add(amount)
+                .ok_or(Error::InvalidAmount)?;
+
+            // Update balances
+            self.set_balance(from, new_from_balance);
+            self.set_balance(to, new_to_balance);
+
+            Ok(())
+        }
+
+        /// Store an account's balance, removing the entry once it reaches zero
+        ///
+        /// Removing instead of storing `0` frees the entry's storage deposit;
+        /// `balance_of` still reads it back as 0.
+        fn set_balance(&mut self, account: AccountId, balance: u128) {
+            let was_holder = self.balances.contains(account);
+            if balance == 0 {
+                self.balances.remove(account);
+                if was_holder {
+                    self.remove_holder(account);
+                }
+            } else {
+                self.balances.insert(account, &balance);
+                if !was_holder {
+                    self.add_holder(account);
+                }
+            }
+        }
+
+        /// Append an account to the holders list
+        fn add_holder(&mut self, account: AccountId) {
+            let index = self.holders_count;
+            self.holders.insert(index, &account);
+            self.holder_index.insert(account, &index);
+            self.holders_count = index.saturating_add(1);
+        }
+
+        /// Remove an account from the holders list, moving the last holder into its slot
+        fn remove_holder(&mut self, account: AccountId) {
+            let Some(index) = self.holder_index.take(account) else {
+                return;
+            };
+
+            let last = self.holders_count.saturating_sub(1);
+            if index != last {
+                if let Some(moved) = self.holders.get(last) {
+                    self.holders.insert(index, &moved);
+                    self.holder_index.insert(moved, &index);
+                }
+            }
+            self.holders.remove(last);
+            self.holders_count = last;
+        }
+
+        /// `bps` basis points of `amount`, rounded down
+        fn bps_of(amount: u128, bps: u16) -> u128 {
+            // Split the multiplication so `amount * bps` cannot overflow
+            let bps = u128::from(bps);
+            let max_bps = u128::from(MAX_BPS);
+            (amount / max_bps) * bps + (amount % max_bps) * bps / max_bps
+        }
+
+        /// Remove a staged transfer owned by the caller and unlock its amount
+        fn take_pending_transfer(&mut self, id: u32) -> Result<PendingTransfer> {
+            let pending = self.pending_transfers.get(id).ok_or(Error::TransferNotFound)?;
+            if self.env().caller() != pending.from {
+                return Err(Error::NotSender);
+            }
+
+            let new_staged = self.staged_balance(pending.from).saturating_sub(pending.amount);
+            self.staged.insert(pending.from, &new_staged);
+            self.pending_transfers.remove(id);
+
+            Ok(pending)
+        }
+    }
+
+    /// PSP22 compatibility layer delegating to the contract's own messages
+    impl PSP22 for TokenBalance {
+        #[ink(message)]
+        fn total_supply(&self) -> u128 {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> u128 {
+            TokenBalance::balance_of(self, owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> u128 {
+            TokenBalance::allowance(self, owner, spender)
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: u128, _data: Vec<u8>) -> core::result::Result<(), PSP22Error> {
+            Ok(TokenBalance::transfer(self, to, value)?)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: u128,
+            _data: Vec<u8>,
+        ) -> core::result::Result<(), PSP22Error> {
+            Ok(TokenBalance::transfer_from(self, from, to, value)?)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: u128) -> core::result::Result<(), PSP22Error> {
+            Ok(TokenBalance::approve(self, spender, value)?)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test, DefaultEnvironment};
+
+        /// Address the contract under test is deployed at
+        fn contract_id() -> AccountId {
+            AccountId::from([0x42; 32])
+        }
+
+        fn default_accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn set_caller(caller: AccountId) {
+            test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        /// Deploy a fresh contract owned by `owner`
+        fn deploy_as(owner: AccountId) -> TokenBalance {
+            test::set_callee::<DefaultEnvironment>(contract_id());
+            set_caller(owner);
+            TokenBalance::new()
+        }
+
+        /// Deploy a contract owned by alice and mint `amount` to `holder`
+        fn deploy_with(holder: AccountId, amount: u128) -> TokenBalance {
+            let mut contract = deploy_as(default_accounts().alice);
+            contract.mint(holder, amount).unwrap();
+            contract
+        }
+
+        fn advance_blocks(blocks: u32) {
+            for _ in 0..blocks {
+                test::advance_block::<DefaultEnvironment>();
+            }
+        }
+
+        /// Decode every recorded event of type `E`, oldest first
+        fn emitted<E: ink::env::Event + scale::Decode>() -> Vec<E> {
+            test::recorded_events()
+                .filter(|event| E::SIGNATURE_TOPIC.is_some_and(|topic| event.topics.first() == Some(&topic.to_vec())))
+                .map(|event| E::decode(&mut &event.data[..]).expect("recorded event decodes"))
+                .collect()
+        }
+
+        /// Account of a dev keypair, which can sign permits
+        fn account_of(signer: &ink_e2e::Keypair) -> AccountId {
+            AccountId::from(signer.public_key().0)
+        }
+
+        /// Sign the blake2x256 hash of `message`, as the contract verifies it
+        fn sign<T: scale::Encode>(signer: &ink_e2e::Keypair, message: &T) -> [u8; 64] {
+            let mut hash = [0u8; 32];
+            ink::env::hash_encoded::<Blake2x256, _>(message, &mut hash);
+            signer.sign(&hash).0
+        }
+
+        /// Deploy a contract owned by alice whose mints need 2 of alice, bob and charlie
+        fn deploy_with_quorum() -> TokenBalance {
+            let accounts = default_accounts();
+            test::set_callee::<DefaultEnvironment>(contract_id());
+            set_caller(accounts.alice);
+            TokenBalance::new_with_owners(vec![accounts.alice, accounts.bob, accounts.charlie], 2).unwrap()
+        }
+
+        #[ink::test]
+        fn burn_reduces_balance_and_supply() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(40), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 60);
+            assert_eq!(contract.total_supply(), 60);
+        }
+
+        #[ink::test]
+        fn burn_reports_supply_underflow() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            // Break the invariant that balances never add up to more than the supply
+            contract.total_supply = 10;
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(40), Err(Error::SupplyUnderflow));
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+            assert_eq!(contract.total_supply(), 10);
+        }
+
+        #[ink::test]
+        fn transfers_above_confirm_threshold_must_be_staged() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_confirm_threshold(100).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 101), Err(Error::ConfirmationRequired));
+            assert_eq!(contract.transfer(accounts.charlie, 100), Ok(()));
+
+            let id = contract.stage_transfer(accounts.charlie, 900).unwrap();
+            assert_eq!(contract.staged_balance(accounts.bob), 900);
+            // Staged funds stay locked until the transfer is confirmed
+            assert_eq!(contract.transfer(accounts.charlie, 1), Err(Error::InsufficientBalance));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.confirm_transfer(id), Err(Error::NotSender));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.confirm_transfer(id), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.balance_of(accounts.charlie), 1_000);
+            assert_eq!(contract.staged_balance(accounts.bob), 0);
+            assert_eq!(contract.pending_transfer(id), None);
+            assert_eq!(contract.transfer_history(accounts.charlie).len(), 2);
+        }
+
+        #[ink::test]
+        fn cancelled_transfers_unlock_their_amount() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            let id = contract.stage_transfer(accounts.charlie, 600).unwrap();
+            assert_eq!(contract.cancel_transfer(id), Ok(()));
+            assert_eq!(contract.staged_balance(accounts.bob), 0);
+            assert_eq!(contract.confirm_transfer(id), Err(Error::TransferNotFound));
+            assert_eq!(contract.transfer(accounts.charlie, 1_000), Ok(()));
+        }
+
+        #[ink::test]
+        fn expired_staged_transfers_cannot_be_confirmed() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_confirm_window(2).unwrap();
+
+            set_caller(accounts.bob);
+            let id = contract.stage_transfer(accounts.charlie, 600).unwrap();
+            assert_eq!(contract.pending_transfer(id).unwrap().expires_at, 2);
+
+            advance_blocks(3);
+            assert_eq!(contract.confirm_transfer(id), Err(Error::ConfirmationExpired));
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn transfer_from_and_batches_reject_amounts_above_confirm_threshold() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_confirm_threshold(100).unwrap();
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 500).unwrap();
+            assert_eq!(
+                contract.batch_transfer(vec![(accounts.charlie, 50), (accounts.django, 101)]),
+                Err(Error::ConfirmationRequired)
+            );
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 101), Err(Error::ConfirmationRequired));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 500);
+        }
+
+        #[ink::test]
+        fn encumbrances_break_down_unspendable_balance() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.alice, 1_000);
+            contract.create_vesting(accounts.bob, 300, 10).unwrap();
+            contract.mint(accounts.bob, 200).unwrap();
+
+            set_caller(accounts.bob);
+            contract.stage_transfer(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.alice);
+            contract.lock(accounts.bob, 50).unwrap();
+
+            let encumbrances = contract.encumbrances(accounts.bob);
+            assert_eq!(encumbrances, Encumbrances { staged: 100, unvested: 300, locked: 50 });
+            assert_eq!(encumbrances.total(), 450);
+            assert_eq!(contract.balance_of(accounts.bob), 500);
+            assert_eq!(contract.spendable_balance(accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn batch_transfer_rejects_empty_batches() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.batch_transfer(Vec::new()), Err(Error::EmptyBatch));
+        }
+
+        #[ink::test]
+        fn batch_transfer_rejects_batches_above_max_batch_len() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_max_batch_len(2).unwrap();
+
+            set_caller(accounts.bob);
+            let batch = vec![(accounts.charlie, 10), (accounts.django, 10), (accounts.eve, 10)];
+            assert_eq!(contract.batch_transfer(batch), Err(Error::BatchTooLarge));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10), (accounts.django, 10)]), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 980);
+        }
+
+        #[ink::test]
+        fn airdrop_mints_to_a_full_batch_of_recipients() {
+            let mut contract = deploy_as(default_accounts().alice);
+            let recipients: Vec<AccountId> = (1..=100u8).map(|byte| AccountId::from([byte; 32])).collect();
+
+            assert_eq!(contract.airdrop(recipients.clone(), 5), Ok(()));
+            assert_eq!(contract.total_supply(), 500);
+            assert!(recipients.iter().all(|recipient| contract.balance_of(*recipient) == 5));
+            assert_eq!(emitted::<TokensMinted>().len(), 100);
+
+            let too_many: Vec<AccountId> = (1..=101u8).map(|byte| AccountId::from([byte; 32])).collect();
+            assert_eq!(contract.airdrop(too_many, 5), Err(Error::BatchTooLarge));
+        }
+
+        #[ink::test]
+        fn airdrop_rejects_a_total_that_overflows() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            let amount_each = u128::MAX / 2 + 1;
+            assert_eq!(contract.airdrop(vec![accounts.bob, accounts.charlie], amount_each), Err(Error::InvalidAmount));
+            assert_eq!(contract.total_supply(), 0);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn airdrop_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.airdrop(vec![accounts.bob], 5), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn psp22_selectors_match_the_standard() {
+            use ink::reflect::DispatchableMessageInfo;
+
+            // Selectors listed in the PSP22 specification
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0x162df8c2>>::LABEL, "PSP22::total_supply");
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0x6568382f>>::LABEL, "PSP22::balance_of");
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0x4d47d921>>::LABEL, "PSP22::allowance");
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0xdb20f9f5>>::LABEL, "PSP22::transfer");
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0x54b3c76e>>::LABEL, "PSP22::transfer_from");
+            assert_eq!(<TokenBalance as DispatchableMessageInfo<0xb20f1bbd>>::LABEL, "PSP22::approve");
+        }
+
+        #[ink::test]
+        fn psp22_messages_delegate_and_map_errors() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            assert_eq!(PSP22::transfer(&mut contract, accounts.charlie, 40, Vec::new()), Ok(()));
+            assert_eq!(PSP22::balance_of(&contract, accounts.charlie), 40);
+            assert_eq!(PSP22::total_supply(&contract), 100);
+            assert_eq!(
+                PSP22::transfer(&mut contract, accounts.charlie, 1_000, Vec::new()),
+                Err(PSP22Error::InsufficientBalance)
+            );
+            assert_eq!(
+                PSP22::transfer(&mut contract, accounts.bob, 1, Vec::new()),
+                Err(PSP22Error::Custom(String::from("TransferToSelf")))
+            );
+
+            assert_eq!(PSP22::approve(&mut contract, accounts.charlie, 10), Ok(()));
+            assert_eq!(PSP22::allowance(&contract, accounts.bob, accounts.charlie), 10);
+
+            set_caller(accounts.charlie);
+            assert_eq!(
+                PSP22::transfer_from(&mut contract, accounts.bob, accounts.django, 11, Vec::new()),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(PSP22::transfer_from(&mut contract, accounts.bob, accounts.django, 10, Vec::new()), Ok(()));
+            assert_eq!(PSP22::balance_of(&contract, accounts.django), 10);
+        }
+
+        #[ink::test]
+        fn vesting_releases_linearly_as_blocks_pass() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.alice, 1_000);
+            assert_eq!(contract.create_vesting(accounts.bob, 100, 10), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+            assert_eq!(contract.spendable_balance(accounts.bob), 0);
+
+            advance_blocks(3);
+            assert_eq!(contract.vested_amount(accounts.bob), 30);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.release_vested(), Ok(30));
+            assert_eq!(contract.release_vested(), Err(Error::NothingToRelease));
+            assert_eq!(contract.spendable_balance(accounts.bob), 30);
+            assert_eq!(contract.transfer(accounts.charlie, 31), Err(Error::InsufficientBalance));
+
+            advance_blocks(20);
+            assert_eq!(contract.vested_amount(accounts.bob), 100);
+            assert_eq!(contract.release_vested(), Ok(70));
+            assert_eq!(contract.spendable_balance(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn vesting_rejects_a_second_unreleased_schedule() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.alice, 1_000);
+            contract.create_vesting(accounts.bob, 100, 10).unwrap();
+            assert_eq!(contract.create_vesting(accounts.bob, 100, 10), Err(Error::VestingExists));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.release_vested(), Err(Error::NoVestingSchedule));
+        }
+
+        #[ink::test]
+        fn transfer_fee_is_split_to_the_fee_recipient() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(100).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 1_000), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 9_000);
+            assert_eq!(contract.balance_of(accounts.charlie), 990);
+            assert_eq!(contract.balance_of(accounts.django), 10);
+
+            let fees = emitted::<FeeCollected>();
+            assert_eq!(fees.len(), 1);
+            assert_eq!((fees[0].from, fees[0].to, fees[0].amount), (accounts.bob, accounts.django, 10));
+        }
+
+        #[ink::test]
+        fn zero_fee_and_dust_transfers_pay_no_fee() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 1_000).unwrap();
+            assert_eq!(contract.balance_of(accounts.charlie), 1_000);
+
+            set_caller(accounts.alice);
+            contract.set_fee_bps(100).unwrap();
+
+            // 1% of 99 rounds down to zero
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 99).unwrap();
+            assert_eq!(contract.balance_of(accounts.charlie), 1_099);
+            assert_eq!(contract.balance_of(accounts.django), 0);
+            assert!(emitted::<FeeCollected>().is_empty());
+        }
+
+        #[ink::test]
+        fn fee_bps_cannot_exceed_10000() {
+            let mut contract = deploy_as(default_accounts().alice);
+            assert_eq!(contract.set_fee_bps(10_001), Err(Error::InvalidFee));
+            assert_eq!(contract.set_fee_bps(10_000), Ok(()));
+        }
+
+        #[ink::test]
+        fn transfer_fee_applies_to_every_transfer_path() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(1_000).unwrap();
+
+            set_caller(accounts.bob);
+            contract.batch_transfer(vec![(accounts.charlie, 100), (accounts.eve, 100)]).unwrap();
+            contract.batch_transfer_lenient(vec![(accounts.charlie, 100)]).unwrap();
+            let id = contract.stage_transfer(accounts.charlie, 100).unwrap();
+            contract.confirm_transfer(id).unwrap();
+            contract.approve(accounts.frank, 100).unwrap();
+
+            set_caller(accounts.frank);
+            contract.transfer_from(accounts.bob, accounts.charlie, 100).unwrap();
+
+            assert_eq!(contract.balance_of(accounts.bob), 9_500);
+            assert_eq!(contract.balance_of(accounts.charlie), 360);
+            assert_eq!(contract.balance_of(accounts.eve), 90);
+            assert_eq!(contract.balance_of(accounts.django), 50);
+        }
+
+        #[ink::test]
+        fn guarded_messages_reject_reentrant_calls() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 100).unwrap();
+
+            // A nested call finds the guard still held by the outer call
+            contract.locked = true;
+            assert_eq!(contract.transfer(accounts.charlie, 10), Err(Error::ReentrantCall));
+            assert_eq!(contract.burn(10), Err(Error::ReentrantCall));
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10)]), Err(Error::ReentrantCall));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.charlie, 10), Err(Error::ReentrantCall));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+        }
+
+        #[ink::test]
+        fn reentrancy_guard_is_released_after_each_call() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert!(!contract.locked);
+            assert_eq!(contract.transfer(accounts.charlie, 1_000), Err(Error::InsufficientBalance));
+            assert!(!contract.locked);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+        }
+
+        #[ink::test]
+        fn mint_with_permit_mints_once_per_owner_signature() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::alice();
+            let owner = account_of(&signer);
+            let mut contract = deploy_as(owner);
+            let deadline: Timestamp = 1_000;
+            let signature = sign(&signer, &(MINT_PERMIT_DOMAIN, contract_id(), accounts.bob, 50u128, deadline, 0u64));
+
+            // Anyone can relay the permit
+            set_caller(accounts.charlie);
+            assert_eq!(contract.mint_with_permit(accounts.bob, 50, deadline, signature), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 50);
+            assert_eq!(contract.nonce_of(owner), 1);
+
+            // The nonce moved on, so the same signature no longer verifies
+            assert_eq!(contract.mint_with_permit(accounts.bob, 50, deadline, signature), Err(Error::InvalidSignature));
+            assert_eq!(contract.balance_of(accounts.bob), 50);
+        }
+
+        #[ink::test]
+        fn mint_with_permit_rejects_an_expired_deadline() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::alice();
+            let owner = account_of(&signer);
+            let mut contract = deploy_as(owner);
+            let deadline: Timestamp = 1_000;
+            let signature = sign(&signer, &(MINT_PERMIT_DOMAIN, contract_id(), accounts.bob, 50u128, deadline, 0u64));
+
+            test::set_block_timestamp::<DefaultEnvironment>(deadline + 1);
+            assert_eq!(contract.mint_with_permit(accounts.bob, 50, deadline, signature), Err(Error::PermitExpired));
+            assert_eq!(contract.nonce_of(owner), 0);
+        }
+
+        #[ink::test]
+        fn mint_with_permit_rejects_other_signers() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(account_of(&ink_e2e::alice()));
+            let deadline: Timestamp = 1_000;
+            let signature = sign(
+                &ink_e2e::bob(),
+                &(MINT_PERMIT_DOMAIN, contract_id(), accounts.bob, 50u128, deadline, 0u64),
+            );
+
+            assert_eq!(contract.mint_with_permit(accounts.bob, 50, deadline, signature), Err(Error::InvalidSignature));
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn permit_sets_the_allowance_once_per_signature() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::bob();
+            let owner = account_of(&signer);
+            let mut contract = deploy_as(accounts.alice);
+            let deadline: Timestamp = 1_000;
+            let signature = sign(&signer, &(PERMIT_DOMAIN, contract_id(), owner, accounts.charlie, 75u128, 0u64, deadline));
+
+            // Anyone can relay the permit
+            set_caller(accounts.django);
+            assert_eq!(contract.permit(owner, accounts.charlie, 75, deadline, signature), Ok(()));
+            assert_eq!(contract.allowance(owner, accounts.charlie), 75);
+            assert_eq!(contract.permit_nonce(owner), 1);
+
+            let approvals = emitted::<Approval>();
+            assert_eq!(approvals.len(), 1);
+            assert_eq!((approvals[0].owner, approvals[0].spender, approvals[0].amount), (owner, accounts.charlie, 75));
+
+            // The nonce moved on, so the same signature no longer verifies
+            assert_eq!(contract.permit(owner, accounts.charlie, 75, deadline, signature), Err(Error::InvalidSignature));
+        }
+
+        #[ink::test]
+        fn permit_rejects_an_expired_deadline() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::bob();
+            let owner = account_of(&signer);
+            let mut contract = deploy_as(accounts.alice);
+            let deadline: Timestamp = 1_000;
+            let signature = sign(&signer, &(PERMIT_DOMAIN, contract_id(), owner, accounts.charlie, 75u128, 0u64, deadline));
+
+            test::set_block_timestamp::<DefaultEnvironment>(deadline + 1);
+            assert_eq!(contract.permit(owner, accounts.charlie, 75, deadline, signature), Err(Error::PermitExpired));
+            assert_eq!(contract.allowance(owner, accounts.charlie), 0);
+        }
+
+        #[ink::test]
+        fn permit_rejects_a_signature_by_someone_else() {
+            let accounts = default_accounts();
+            let owner = account_of(&ink_e2e::bob());
+            let mut contract = deploy_as(accounts.alice);
+            let deadline: Timestamp = 1_000;
+            let signature = sign(
+                &ink_e2e::charlie(),
+                &(PERMIT_DOMAIN, contract_id(), owner, accounts.charlie, 75u128, 0u64, deadline),
+            );
+
+            assert_eq!(contract.permit(owner, accounts.charlie, 75, deadline, signature), Err(Error::InvalidSignature));
+            assert_eq!(contract.allowance(owner, accounts.charlie), 0);
+            assert_eq!(contract.permit_nonce(owner), 0);
+        }
+
+        #[ink::test]
+        fn force_transfer_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.force_transfer(accounts.bob, accounts.charlie, 10), Err(Error::NotOwner));
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn force_transfer_moves_funds_and_emits_event() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            // Pause and blacklist do not stop the owner
+            contract.pause().unwrap();
+            contract.blacklist(accounts.bob).unwrap();
+
+            assert_eq!(contract.force_transfer(accounts.bob, accounts.charlie, 60), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 40);
+            assert_eq!(contract.balance_of(accounts.charlie), 60);
+
+            let forced = emitted::<ForcedTransfer>();
+            assert_eq!(forced.len(), 1);
+            assert_eq!(
+                (forced[0].from, forced[0].to, forced[0].amount, forced[0].by),
+                (accounts.bob, accounts.charlie, 60, accounts.alice)
+            );
+
+            assert_eq!(contract.force_transfer(accounts.bob, accounts.charlie, 41), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn spendable_balance_reflects_encumbrances_blacklist_and_pause() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.mint(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.bob);
+            contract.stage_transfer(accounts.django, 30).unwrap();
+            assert_eq!(contract.spendable_balance(accounts.bob), 70);
+
+            set_caller(accounts.alice);
+            contract.blacklist(accounts.charlie).unwrap();
+            assert_eq!(contract.spendable_balance(accounts.charlie), 0);
+
+            contract.pause().unwrap();
+            assert_eq!(contract.spendable_balance(accounts.bob), 0);
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn finish_minting_disables_minting_but_not_transfers() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            assert!(!contract.is_minting_finished());
+
+            assert_eq!(contract.finish_minting(), Ok(()));
+            assert!(contract.is_minting_finished());
+            assert_eq!(contract.mint(accounts.bob, 1), Err(Error::MintingFinished));
+            assert_eq!(contract.airdrop(vec![accounts.bob], 1), Err(Error::MintingFinished));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 40), Ok(()));
+            assert_eq!(contract.balance_of(accounts.charlie), 40);
+        }
+
+        #[ink::test]
+        fn finish_minting_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.finish_minting(), Err(Error::NotOwner));
+            assert!(!contract.is_minting_finished());
+        }
+
+        #[ink::test]
+        fn lenient_batch_executes_only_valid_legs() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.blacklist(accounts.frank).unwrap();
+
+            set_caller(accounts.bob);
+            let results = contract.batch_transfer_lenient(vec![
+                (accounts.charlie, 300),
+                (accounts.bob, 10),
+                (accounts.django, 0),
+                (accounts.frank, 10),
+                (accounts.eve, 800),
+                (accounts.django, 200),
+            ]);
+            assert_eq!(results, Ok(vec![true, false, false, false, false, true]));
+            assert_eq!(contract.balance_of(accounts.bob), 500);
+            assert_eq!(contract.balance_of(accounts.charlie), 300);
+            assert_eq!(contract.balance_of(accounts.django), 200);
+            assert_eq!(contract.balance_of(accounts.eve), 0);
+            assert_eq!(contract.balance_of(accounts.frank), 0);
+        }
+
+        #[ink::test]
+        fn lenient_batch_skips_legs_that_need_confirmation() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_confirm_threshold(100).unwrap();
+
+            set_caller(accounts.bob);
+            let results = contract.batch_transfer_lenient(vec![(accounts.charlie, 101), (accounts.django, 100)]);
+            assert_eq!(results, Ok(vec![false, true]));
+            assert_eq!(contract.balance_of(accounts.bob), 900);
+        }
+
+        #[ink::test]
+        fn lenient_batch_skips_every_leg_while_paused() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.pause().unwrap();
+
+            set_caller(accounts.bob);
+            let results = contract.batch_transfer_lenient(vec![(accounts.charlie, 10), (accounts.django, 10)]);
+            assert_eq!(results, Ok(vec![false, false]));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+        }
+
+        #[ink::test]
+        fn batch_transfer_checks_every_leg_like_transfer() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.blacklist(accounts.frank).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(
+                contract.batch_transfer(vec![(accounts.charlie, 10), (accounts.frank, 10)]),
+                Err(Error::InvalidAmount)
+            );
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+
+            set_caller(accounts.alice);
+            contract.pause().unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10)]), Err(Error::InvalidAmount));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+        }
+
+        #[ink::test]
+        fn allowance_can_be_spent_until_its_expiry_block() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            contract.approve_with_expiry(accounts.charlie, 100, 5).unwrap();
+            contract.approve(accounts.django, 10).unwrap();
+            assert_eq!(contract.allowance_expiry(accounts.bob, accounts.charlie), Some(5));
+            assert_eq!(contract.allowance_expiry(accounts.bob, accounts.django), None);
+
+            advance_blocks(5);
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.eve, 40), Ok(()));
+
+            advance_blocks(1);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.eve, 40), Err(Error::AllowanceExpired));
+            assert_eq!(contract.balance_of(accounts.eve), 40);
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 60);
+        }
+
+        #[ink::test]
+        fn mint_is_capped_per_call_by_max_mint_per_tx() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            assert_eq!(contract.set_max_mint_per_tx(Some(100)), Ok(()));
+            assert_eq!(contract.max_mint_per_tx(), Some(100));
+
+            assert_eq!(contract.mint(accounts.bob, 100), Ok(100));
+            assert_eq!(contract.mint(accounts.bob, 101), Err(Error::MintLimitExceeded));
+            assert_eq!(contract.airdrop(vec![accounts.bob, accounts.charlie], 51), Err(Error::MintLimitExceeded));
+            assert_eq!(contract.total_supply(), 100);
+
+            contract.set_max_mint_per_tx(None).unwrap();
+            assert_eq!(contract.mint(accounts.bob, 101), Ok(201));
+        }
+
+        #[ink::test]
+        fn max_mint_per_tx_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_max_mint_per_tx(Some(1)), Err(Error::NotOwner));
+            assert_eq!(contract.max_mint_per_tx(), None);
+        }
+
+        #[ink::test]
+        fn large_outflow_in_one_block_pauses_transfers() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_outflow_threshold(Some(500)).unwrap();
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 400).unwrap();
+            // The outflow starts over in every block
+            advance_blocks(1);
+            contract.transfer(accounts.charlie, 400).unwrap();
+            assert!(!contract.is_paused());
+
+            // The transfer that crosses the threshold still goes through
+            assert_eq!(contract.transfer(accounts.charlie, 200), Ok(()));
+            assert!(contract.is_paused());
+            assert_eq!(contract.balance_of(accounts.charlie), 1_000);
+
+            let paused = emitted::<Paused>();
+            assert_eq!(paused.len(), 1);
+            assert_eq!(paused[0].by, contract_id());
+            assert_eq!(contract.transfer(accounts.charlie, 1), Err(Error::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn batch_and_confirmed_transfers_count_toward_outflow() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_outflow_threshold(Some(500)).unwrap();
+
+            set_caller(accounts.bob);
+            contract.batch_transfer(vec![(accounts.charlie, 300), (accounts.django, 300)]).unwrap();
+            assert!(contract.is_paused());
+
+            set_caller(accounts.alice);
+            contract.unpause().unwrap();
+            advance_blocks(1);
+
+            set_caller(accounts.bob);
+            let id = contract.stage_transfer(accounts.charlie, 600).unwrap();
+            assert_eq!(contract.confirm_transfer(id), Ok(()));
+            assert!(contract.is_paused());
+
+            set_caller(accounts.alice);
+            contract.unpause().unwrap();
+            advance_blocks(1);
+
+            // Only executed lenient legs count
+            set_caller(accounts.bob);
+            contract.batch_transfer_lenient(vec![(accounts.charlie, 500), (accounts.bob, 100)]).unwrap();
+            assert!(!contract.is_paused());
+            contract.batch_transfer_lenient(vec![(accounts.charlie, 1)]).unwrap();
+            assert!(contract.is_paused());
+        }
+
+        #[ink::test]
+        fn revoke_allowance_removes_the_storage_entry() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 100).unwrap();
+            assert!(contract.allowances.contains((accounts.bob, accounts.charlie)));
+
+            assert_eq!(contract.revoke_allowance(accounts.charlie), Ok(()));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 0);
+            assert!(!contract.allowances.contains((accounts.bob, accounts.charlie)));
+
+            let approval = emitted::<Approval>().pop().unwrap();
+            assert_eq!((approval.owner, approval.spender, approval.amount), (accounts.bob, accounts.charlie, 0));
+        }
+
+        #[ink::test]
+        fn recover_token_is_owner_only_and_refuses_own_token() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            let foreign = AccountId::from([0x77; 32]);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.recover_token(foreign, accounts.bob, 10), Err(Error::NotOwner));
+
+            // Recovering a foreign token calls into it, which the off-chain environment cannot do
+            set_caller(accounts.alice);
+            assert_eq!(contract.recover_token(contract_id(), accounts.bob, 10), Err(Error::CannotRecoverOwnToken));
+        }
+
+        #[ink::test]
+        fn total_burned_accumulates_across_burns() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.burn(25).unwrap();
+            contract.burn(35).unwrap();
+            assert_eq!(contract.total_burned(), 60);
+            assert_eq!(contract.total_supply(), 40);
+            assert_eq!(contract.balance_of(accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn faucet_claims_are_rate_limited() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.faucet_claim(), Err(Error::InvalidAmount));
+
+            set_caller(accounts.alice);
+            contract.set_faucet(50, 10).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.faucet_claim(), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 50);
+            assert_eq!(contract.faucet_claim(), Err(Error::FaucetCooldown));
+
+            advance_blocks(9);
+            assert_eq!(contract.faucet_claim(), Err(Error::FaucetCooldown));
+            advance_blocks(1);
+            assert_eq!(contract.faucet_claim(), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn faucet_is_configured_by_the_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_faucet(50, 10), Err(Error::NotOwner));
+            assert_eq!(contract.faucet_amount(), 0);
+        }
+
+        #[ink::test]
+        fn mint_returns_the_recipients_new_balance() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            assert_eq!(contract.mint(accounts.bob, 100), Ok(100));
+            assert_eq!(contract.mint(accounts.bob, 50), Ok(150));
+            assert_eq!(contract.balance_of(accounts.bob), 150);
+        }
+
+        #[ink::test]
+        fn minting_to_a_blacklisted_account_fails() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.blacklist(accounts.bob).unwrap();
+
+            assert_eq!(contract.mint(accounts.bob, 10), Err(Error::Blacklisted));
+            assert_eq!(contract.airdrop(vec![accounts.charlie, accounts.bob], 10), Err(Error::Blacklisted));
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+
+            assert_eq!(contract.mint(accounts.django, 10), Ok(10));
+        }
+
+        #[ink::test]
+        fn blacklist_manager_can_blacklist_until_removed() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            assert_eq!(contract.add_blacklist_manager(accounts.bob), Ok(()));
+            assert!(contract.is_blacklist_manager(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.blacklist(accounts.charlie), Ok(()));
+            assert!(contract.is_blacklisted(accounts.charlie));
+            assert_eq!(contract.unblacklist(accounts.charlie), Ok(()));
+            assert!(!contract.is_blacklisted(accounts.charlie));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.remove_blacklist_manager(accounts.bob), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.blacklist(accounts.charlie), Err(Error::NotOwner));
+            assert!(!contract.is_blacklisted(accounts.charlie));
+
+            assert_eq!(emitted::<BlacklistManagerAdded>()[0].manager, accounts.bob);
+            assert_eq!(emitted::<BlacklistManagerRemoved>()[0].manager, accounts.bob);
+        }
+
+        #[ink::test]
+        fn blacklist_managers_are_appointed_by_the_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.add_blacklist_manager(accounts.bob), Err(Error::NotOwner));
+            assert!(!contract.is_blacklist_manager(accounts.bob));
+        }
+
+        #[ink::test]
+        fn get_config_reflects_the_current_state() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.set_decimals(6).unwrap();
+            contract.mint(accounts.bob, 1_000).unwrap();
+            contract.set_max_mint_per_tx(Some(500)).unwrap();
+            contract.set_max_batch_len(20).unwrap();
+            contract.set_confirm_threshold(300).unwrap();
+            contract.set_confirm_window(50).unwrap();
+            contract.set_outflow_threshold(Some(2_000)).unwrap();
+            contract.set_fee_bps(25).unwrap();
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_faucet(10, 100).unwrap();
+            contract.pause().unwrap();
+
+            assert_eq!(contract.get_config(), ContractConfig {
+                owner: accounts.alice,
+                total_supply: 1_000,
+                decimals: 6,
+                paused: true,
+                minting_finished: false,
+                max_mint_per_tx: Some(500),
+                max_batch_len: 20,
+                confirm_threshold: 300,
+                confirm_window: 50,
+                outflow_threshold: Some(2_000),
+                fee_bps: 25,
+                fee_recipient: accounts.django,
+                faucet_amount: 10,
+                faucet_cooldown_blocks: 100,
+                burn_fee_bps: 0,
+                treasury: accounts.alice,
+            });
+        }
+
+        #[ink::test]
+        fn zeroed_balances_are_removed_from_storage() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.mint(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.django, 100).unwrap();
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert!(!contract.balances.contains(accounts.bob));
+            assert!(contract.balances.contains(accounts.django));
+
+            set_caller(accounts.charlie);
+            contract.burn(100).unwrap();
+            assert_eq!(contract.balance_of(accounts.charlie), 0);
+            assert!(!contract.balances.contains(accounts.charlie));
+        }
+
+        #[ink::test]
+        fn max_allowance_is_not_decremented() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 300).unwrap();
+            contract.approve(accounts.django, u128::MAX).unwrap();
+
+            set_caller(accounts.charlie);
+            contract.transfer_from(accounts.bob, accounts.eve, 100).unwrap();
+            contract.transfer_from(accounts.bob, accounts.eve, 100).unwrap();
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 100);
+
+            set_caller(accounts.django);
+            contract.transfer_from(accounts.bob, accounts.eve, 100).unwrap();
+            contract.transfer_from(accounts.bob, accounts.eve, 100).unwrap();
+            assert_eq!(contract.allowance(accounts.bob, accounts.django), u128::MAX);
+
+            assert_eq!(contract.balance_of(accounts.eve), 400);
+        }
+
+        #[ink::test]
+        fn pause_exempt_accounts_can_transfer_while_paused() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.mint(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.charlie);
+            contract.approve(accounts.bob, 50).unwrap();
+
+            set_caller(accounts.alice);
+            contract.add_pause_exempt(accounts.bob).unwrap();
+            contract.pause().unwrap();
+            assert!(contract.is_pause_exempt(accounts.bob));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.django, 10), Ok(()));
+            // The exempt operator can also move funds it was approved for
+            assert_eq!(contract.transfer_from(accounts.charlie, accounts.django, 10), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer(accounts.django, 10), Err(Error::InvalidAmount));
+
+            set_caller(accounts.alice);
+            contract.remove_pause_exempt(accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.django, 10), Err(Error::InvalidAmount));
+            assert_eq!(contract.balance_of(accounts.django), 20);
+        }
+
+        #[ink::test]
+        fn transfer_from_to_the_owner_itself_keeps_the_allowance() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 50).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.bob, 10), Err(Error::TransferToSelf));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 50);
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn quorum_mint_executes_at_the_threshold() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with_quorum();
+
+            let id = contract.propose_mint(accounts.django, 100).unwrap();
+            assert_eq!(contract.mint_proposal(id).unwrap().approvals, vec![accounts.alice]);
+            assert_eq!(contract.balance_of(accounts.django), 0);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.approve_mint(id), Ok(()));
+            assert!(contract.mint_proposal(id).unwrap().executed);
+            assert_eq!(contract.balance_of(accounts.django), 100);
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.approve_mint(id), Err(Error::ProposalExecuted));
+            assert_eq!(contract.total_supply(), 100);
+        }
+
+        #[ink::test]
+        fn quorum_mint_rejects_repeated_and_outside_approvals() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with_quorum();
+
+            let id = contract.propose_mint(accounts.django, 100).unwrap();
+            assert_eq!(contract.approve_mint(id), Err(Error::AlreadyApproved));
+            assert_eq!(contract.approve_mint(id + 1), Err(Error::ProposalNotFound));
+
+            set_caller(accounts.django);
+            assert_eq!(contract.approve_mint(id), Err(Error::NotCoOwner));
+            assert_eq!(contract.propose_mint(accounts.django, 100), Err(Error::NotCoOwner));
+
+            assert_eq!(contract.balance_of(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn quorum_disables_single_key_minting() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with_quorum();
+            contract.set_faucet(10, 0).unwrap();
+
+            assert_eq!(contract.mint(accounts.bob, 10), Err(Error::QuorumRequired));
+            assert_eq!(contract.mint_and_approve(accounts.bob, 10, accounts.charlie, 5), Err(Error::QuorumRequired));
+            assert_eq!(contract.airdrop(vec![accounts.bob], 10), Err(Error::QuorumRequired));
+            assert_eq!(contract.migrate_balances(vec![(accounts.bob, 10)]), Err(Error::QuorumRequired));
+            assert_eq!(contract.mint_with_permit(accounts.bob, 10, 0, [0; 64]), Err(Error::QuorumRequired));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.faucet_claim(), Err(Error::QuorumRequired));
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn new_with_owners_validates_the_threshold() {
+            let accounts = default_accounts();
+            set_caller(accounts.alice);
+
+            let invalid = |owners: Vec<AccountId>, threshold: u8| TokenBalance::new_with_owners(owners, threshold).err();
+            assert_eq!(invalid(vec![accounts.alice, accounts.bob], 0), Some(Error::InvalidThreshold));
+            assert_eq!(invalid(vec![accounts.alice, accounts.bob], 3), Some(Error::InvalidThreshold));
+            // Duplicate owners only count once
+            assert_eq!(invalid(vec![accounts.alice, accounts.alice, accounts.bob], 3), Some(Error::InvalidThreshold));
+
+            let contract = TokenBalance::new_with_owners(vec![accounts.bob, accounts.alice], 2).unwrap();
+            assert_eq!(contract.owners(), vec![accounts.alice, accounts.bob]);
+            assert_eq!(contract.mint_threshold(), 2);
+        }
+
+        #[ink::test]
+        fn spendable_allowance_is_capped_by_allowance_and_balance() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 40).unwrap();
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.charlie), 40);
+
+            contract.approve(accounts.charlie, 500).unwrap();
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn spendable_allowance_is_zero_for_restricted_owners() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 40).unwrap();
+            contract.approve_with_expiry(accounts.django, 40, 1).unwrap();
+
+            set_caller(accounts.alice);
+            contract.blacklist(accounts.bob).unwrap();
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.charlie), 0);
+
+            contract.unblacklist(accounts.bob).unwrap();
+            contract.pause().unwrap();
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.charlie), 0);
+
+            contract.unpause().unwrap();
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.charlie), 40);
+
+            advance_blocks(2);
+            assert_eq!(contract.spendable_allowance(accounts.bob, accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn transfer_from_rejects_blacklisted_parties() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.alice);
+            contract.blacklist(accounts.bob).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 50), Err(Error::InvalidAmount));
+
+            set_caller(accounts.alice);
+            contract.unblacklist(accounts.bob).unwrap();
+            contract.blacklist(accounts.django).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 50), Err(Error::InvalidAmount));
+            assert_eq!(contract.balance_of(accounts.django), 0);
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn transfer_cooldown_blocks_back_to_back_transfers() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_transfer_cooldown_blocks(3).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(contract.transfer(accounts.charlie, 10), Err(Error::TransferCooldown));
+
+            advance_blocks(2);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Err(Error::TransferCooldown));
+            advance_blocks(1);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(contract.balance_of(accounts.charlie), 20);
+        }
+
+        #[ink::test]
+        fn owner_and_pause_exempt_accounts_skip_the_cooldown() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.alice, 1_000);
+            contract.mint(accounts.bob, 1_000).unwrap();
+            contract.add_pause_exempt(accounts.bob).unwrap();
+            contract.set_transfer_cooldown_blocks(3).unwrap();
+
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+        }
+
+        #[ink::test]
+        fn cooldown_applies_to_batch_delegated_and_confirmed_transfers() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_transfer_cooldown_blocks(3).unwrap();
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 100).unwrap();
+            let id = contract.stage_transfer(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10)]), Ok(()));
+
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10)]), Err(Error::TransferCooldown));
+            assert_eq!(contract.batch_transfer_lenient(vec![(accounts.charlie, 10)]), Err(Error::TransferCooldown));
+            assert_eq!(contract.confirm_transfer(id), Err(Error::TransferCooldown));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 10), Err(Error::TransferCooldown));
+            assert_eq!(contract.balance_of(accounts.bob), 990);
+        }
+
+        #[ink::test]
+        fn burn_for_bridge_emits_the_destination() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            let dest_address = [0xab; 32];
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn_for_bridge(30, 7, dest_address), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 70);
+            assert_eq!(contract.total_supply(), 70);
+
+            let burns = emitted::<BridgeBurn>();
+            assert_eq!(burns.len(), 1);
+            assert_eq!(
+                (burns[0].from, burns[0].amount, burns[0].dest_chain, burns[0].dest_address),
+                (accounts.bob, 30, 7, dest_address)
+            );
+        }
+
+        #[ink::test]
+        fn minted_minus_burned_equals_supply() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.mint(accounts.charlie, 50).unwrap();
+
+            set_caller(accounts.bob);
+            contract.burn(30).unwrap();
+
+            assert_eq!(contract.total_minted(), 150);
+            assert_eq!(contract.total_burned(), 30);
+            assert_eq!(contract.total_supply(), 120);
+            assert_eq!(contract.total_minted() - contract.total_burned(), contract.total_supply());
+        }
+
+        #[ink::test]
+        fn sweep_dust_moves_only_approved_small_balances() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 5);
+            contract.mint(accounts.charlie, 500).unwrap();
+            contract.mint(accounts.django, 5).unwrap();
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.alice, 5).unwrap();
+            set_caller(accounts.charlie);
+            contract.approve(accounts.alice, 500).unwrap();
+
+            set_caller(accounts.alice);
+            let swept = contract.sweep_dust(vec![accounts.bob, accounts.charlie, accounts.django], 10, accounts.eve);
+            assert_eq!(swept, Ok(5));
+            assert_eq!(contract.balance_of(accounts.eve), 5);
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+            assert_eq!(contract.allowance(accounts.bob, accounts.alice), 0);
+            // Over the threshold
+            assert_eq!(contract.balance_of(accounts.charlie), 500);
+            // Not approved
+            assert_eq!(contract.balance_of(accounts.django), 5);
+        }
+
+        #[ink::test]
+        fn sweep_dust_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 5);
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.sweep_dust(vec![accounts.bob], 10, accounts.charlie), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn sweep_dust_skips_accounts_that_cannot_transfer() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 5);
+            contract.mint(accounts.charlie, 5).unwrap();
+
+            for account in [accounts.bob, accounts.charlie] {
+                set_caller(account);
+                contract.approve(accounts.alice, 5).unwrap();
+            }
+
+            set_caller(accounts.alice);
+            contract.blacklist(accounts.bob).unwrap();
+            let swept = contract.sweep_dust(vec![accounts.bob, accounts.charlie], 10, accounts.eve);
+            assert_eq!(swept, Ok(5));
+            assert_eq!(contract.balance_of(accounts.bob), 5);
+            assert_eq!(contract.balance_of(accounts.eve), 5);
+            assert_eq!(contract.transfer_history(accounts.eve).len(), 1);
+            assert!(contract.last_activity_of(accounts.eve).is_some());
+
+            contract.unblacklist(accounts.bob).unwrap();
+            let zero = AccountId::from([0; 32]);
+            assert_eq!(contract.sweep_dust(vec![accounts.bob], 10, zero), Ok(0));
+            assert_eq!(contract.balance_of(zero), 0);
+            assert_eq!(contract.allowance(accounts.bob, accounts.alice), 5);
+
+            contract.pause().unwrap();
+            assert_eq!(contract.sweep_dust(vec![accounts.bob], 10, accounts.eve), Ok(0));
+            assert_eq!(contract.balance_of(accounts.bob), 5);
+        }
+
+        #[ink::test]
+        fn sweep_dust_pays_the_transfer_fee() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(1_000).unwrap();
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.alice, 100).unwrap();
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.sweep_dust(vec![accounts.bob], 1_000, accounts.eve), Ok(100));
+            assert_eq!(contract.balance_of(accounts.eve), 90);
+            assert_eq!(contract.balance_of(accounts.django), 10);
+        }
+
+        #[ink::test]
+        fn can_transfer_reports_the_error_transfer_would_return() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.mint(accounts.charlie, 10).unwrap();
+            contract.set_confirm_threshold(500).unwrap();
+            contract.blacklist(accounts.django).unwrap();
+
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.charlie, 100), Ok(()));
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.bob, 1), Err(Error::TransferToSelf));
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.charlie, 0), Err(Error::InvalidAmount));
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.charlie, 501), Err(Error::ConfirmationRequired));
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.django, 1), Err(Error::InvalidAmount));
+            assert_eq!(contract.can_transfer(accounts.charlie, accounts.bob, 11), Err(Error::InsufficientBalance));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer(accounts.bob, 11), contract.can_transfer(accounts.charlie, accounts.bob, 11));
+
+            set_caller(accounts.alice);
+            contract.set_transfer_cooldown_blocks(5).unwrap();
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 1).unwrap();
+            assert_eq!(contract.can_transfer(accounts.bob, accounts.charlie, 1), Err(Error::TransferCooldown));
+
+            set_caller(accounts.alice);
+            contract.pause().unwrap();
+            assert_eq!(contract.can_transfer(accounts.charlie, accounts.eve, 1), Err(Error::InvalidAmount));
+        }
+
+        #[ink::test]
+        fn migrate_balances_seeds_balances_and_supply() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            let entries = vec![(accounts.bob, 100), (accounts.charlie, 50), (accounts.bob, 25)];
+
+            assert_eq!(contract.migrate_balances(entries), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 125);
+            assert_eq!(contract.balance_of(accounts.charlie), 50);
+            assert_eq!(contract.total_supply(), 175);
+            assert_eq!(contract.total_minted(), 175);
+        }
+
+        #[ink::test]
+        fn migrate_balances_is_rejected_after_close() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.migrate_balances(vec![(accounts.bob, 100)]), Err(Error::NotOwner));
+            assert_eq!(contract.close_migration(), Err(Error::NotOwner));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.close_migration(), Ok(()));
+            assert!(!contract.is_migration_open());
+            assert_eq!(contract.migrate_balances(vec![(accounts.bob, 100)]), Err(Error::MigrationClosed));
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn migrate_balances_counts_against_max_mint_per_tx() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.set_max_mint_per_tx(Some(100)).unwrap();
+
+            let entries = vec![(accounts.bob, 60), (accounts.charlie, 60)];
+            assert_eq!(contract.migrate_balances(entries), Err(Error::MintLimitExceeded));
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn transfer_history_records_both_sides() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            test::set_block_timestamp::<DefaultEnvironment>(7);
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 10).unwrap();
+
+            assert_eq!(contract.transfer_history(accounts.bob), vec![TransferRecord {
+                counterparty: accounts.charlie,
+                amount: 10,
+                timestamp: 7,
+                incoming: false,
+            }]);
+            assert_eq!(contract.transfer_history(accounts.charlie), vec![TransferRecord {
+                counterparty: accounts.bob,
+                amount: 10,
+                timestamp: 7,
+                incoming: true,
+            }]);
+            assert!(contract.transfer_history(accounts.django).is_empty());
+        }
+
+        #[ink::test]
+        fn transfer_history_keeps_the_latest_entries() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            for amount in 1..=12 {
+                contract.transfer(accounts.charlie, amount).unwrap();
+            }
+
+            let amounts: Vec<u128> = contract.transfer_history(accounts.bob).iter().map(|record| record.amount).collect();
+            assert_eq!(amounts, (3..=12).collect::<Vec<u128>>());
+            assert_eq!(contract.transfer_history(accounts.charlie).len(), MAX_TRANSFER_HISTORY);
+        }
+
+        #[ink::test]
+        fn transfer_history_records_the_gross_debit_for_the_sender() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(1_000).unwrap();
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.transfer_history(accounts.bob)[0].amount, 100);
+            assert_eq!(contract.transfer_history(accounts.charlie)[0].amount, 90);
+        }
+
+        #[ink::test]
+        fn transfer_history_skips_recipients_credited_nothing() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(10_000).unwrap();
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.balance_of(accounts.django), 100);
+            assert_eq!(contract.transfer_history(accounts.bob)[0].amount, 100);
+            assert!(contract.transfer_history(accounts.charlie).is_empty());
+            assert_eq!(contract.last_activity_of(accounts.charlie), None);
+            assert!(emitted::<TokensTransferred>().iter().all(|transfer| transfer.to != accounts.charlie));
+        }
+
+        #[ink::test]
+        fn confirmed_staged_transfers_are_recorded() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_confirm_threshold(100).unwrap();
+
+            set_caller(accounts.bob);
+            let id = contract.stage_transfer(accounts.charlie, 500).unwrap();
+            contract.confirm_transfer(id).unwrap();
+
+            assert_eq!(contract.transfer_history(accounts.bob)[0].amount, 500);
+            assert_eq!(contract.transfer_history(accounts.charlie)[0].counterparty, accounts.bob);
+        }
+
+        #[ink::test]
+        fn mint_and_approve_sets_balance_and_allowance() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            assert_eq!(contract.mint_and_approve(accounts.bob, 100, accounts.charlie, 40), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 100);
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 40);
+
+            let mints = emitted::<TokensMinted>();
+            assert_eq!(mints.len(), 1);
+            assert_eq!((mints[0].to, mints[0].amount), (accounts.bob, 100));
+            let approvals = emitted::<Approval>();
+            assert_eq!(approvals.len(), 1);
+            assert_eq!((approvals[0].owner, approvals[0].spender, approvals[0].amount), (accounts.bob, accounts.charlie, 40));
+        }
+
+        #[ink::test]
+        fn mint_and_approve_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.mint_and_approve(accounts.bob, 100, accounts.charlie, 40), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn scaled_readers_return_the_configured_decimals() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            assert_eq!(contract.decimals(), 18);
+
+            contract.set_decimals(6).unwrap();
+            contract.mint(accounts.bob, 1_500_000).unwrap();
+
+            assert_eq!(contract.total_supply_scaled(), (contract.total_supply(), 6));
+            assert_eq!(contract.balance_of_scaled(accounts.bob), (1_500_000, 6));
+            assert_eq!(contract.set_decimals(8), Err(Error::DecimalsLocked));
+            assert_eq!(contract.decimals(), 6);
+        }
+
+        #[ink::test]
+        fn owner_cannot_be_blacklisted() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.add_blacklist_manager(accounts.bob).unwrap();
+
+            assert_eq!(contract.blacklist(accounts.alice), Err(Error::CannotBlacklistOwner));
+            assert_eq!(contract.blacklist(accounts.charlie), Ok(()));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.blacklist(accounts.alice), Err(Error::CannotBlacklistOwner));
+            assert!(!contract.is_blacklisted(accounts.alice));
+            assert!(contract.is_blacklisted(accounts.charlie));
+        }
+
+        #[ink::test]
+        fn mint_event_records_the_minter() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.mint(accounts.bob, 10).unwrap();
+            contract.set_faucet(5, 0).unwrap();
+
+            set_caller(accounts.charlie);
+            contract.faucet_claim().unwrap();
+
+            let minters: Vec<(AccountId, AccountId)> = emitted::<TokensMinted>().iter()
+                .map(|mint| (mint.to, mint.minter))
+                .collect();
+            assert_eq!(minters, vec![(accounts.bob, accounts.alice), (accounts.charlie, accounts.charlie)]);
+        }
+
+        #[ink::test]
+        fn quorum_mint_event_records_the_approving_co_owner() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with_quorum();
+            let id = contract.propose_mint(accounts.django, 100).unwrap();
+
+            set_caller(accounts.bob);
+            contract.approve_mint(id).unwrap();
+
+            let mints = emitted::<TokensMinted>();
+            assert_eq!(mints.len(), 1);
+            assert_eq!((mints[0].to, mints[0].amount, mints[0].minter), (accounts.django, 100, accounts.bob));
+        }
+
+        #[ink::test]
+        fn locked_balance_cannot_be_moved_until_unlocked() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            assert_eq!(contract.lock(accounts.bob, 70), Ok(()));
+            assert_eq!(contract.locked_balance(accounts.bob), 70);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.transfer(accounts.django, 31), Err(Error::InsufficientBalance));
+            assert_eq!(contract.transfer(accounts.django, 30), Ok(()));
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 1), Err(Error::InsufficientBalance));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.unlock(accounts.bob, 70), Ok(()));
+            assert_eq!(contract.locked_balance(accounts.bob), 0);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.django, 70), Ok(()));
+        }
+
+        #[ink::test]
+        fn lock_and_unlock_check_their_amounts() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            assert_eq!(contract.lock(accounts.bob, 101), Err(Error::InsufficientBalance));
+            assert_eq!(contract.unlock(accounts.bob, 1), Err(Error::InvalidAmount));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.lock(accounts.bob, 10), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn batch_approve_sets_every_allowance_in_order() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            let approvals = vec![(accounts.charlie, 10), (accounts.django, 20), (accounts.charlie, 30)];
+            assert_eq!(contract.batch_approve(approvals), Ok(()));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 30);
+            assert_eq!(contract.allowance(accounts.bob, accounts.django), 20);
+            assert_eq!(emitted::<Approval>().len(), 3);
+        }
+
+        #[ink::test]
+        fn batch_approve_rejects_self_approval_and_empty_lists() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.batch_approve(vec![(accounts.charlie, 5), (accounts.bob, 5)]), Err(Error::SelfApproval));
+            assert_eq!(contract.allowance(accounts.bob, accounts.bob), 0);
+            assert_eq!(contract.batch_approve(Vec::new()), Err(Error::EmptyBatch));
+        }
+
+        #[ink::test]
+        fn circulating_supply_leaves_out_excluded_accounts() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.alice, 600);
+            contract.mint(accounts.bob, 400).unwrap();
+            assert_eq!(contract.circulating_supply(), 1_000);
+
+            assert_eq!(contract.exclude_from_circulation(accounts.alice), Ok(()));
+            assert!(contract.is_excluded_from_circulation(accounts.alice));
+            assert_eq!(contract.circulating_supply(), 400);
+            // Excluding twice does not subtract the balance twice
+            contract.exclude_from_circulation(accounts.alice).unwrap();
+            assert_eq!(contract.circulating_supply(), 400);
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.alice, 100).unwrap();
+            assert_eq!(contract.circulating_supply(), 300);
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.include_in_circulation(accounts.alice), Ok(()));
+            assert!(!contract.is_excluded_from_circulation(accounts.alice));
+            assert_eq!(contract.circulating_supply(), 1_000);
+        }
+
+        #[ink::test]
+        fn circulation_exclusions_are_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.exclude_from_circulation(accounts.bob), Err(Error::NotOwner));
+            assert_eq!(contract.include_in_circulation(accounts.bob), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn balances_add_up_exactly_across_debit_paths() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 100).unwrap();
+            contract.burn(50).unwrap();
+            contract.approve(accounts.charlie, 200).unwrap();
+
+            set_caller(accounts.charlie);
+            contract.transfer_from(accounts.bob, accounts.django, 150).unwrap();
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 50);
+
+            set_caller(accounts.bob);
+            contract.batch_transfer(vec![(accounts.charlie, 100), (accounts.django, 100)]).unwrap();
+
+            assert_eq!(contract.balance_of(accounts.bob), 500);
+            assert_eq!(contract.balance_of(accounts.charlie), 200);
+            assert_eq!(contract.balance_of(accounts.django), 250);
+            assert_eq!(contract.total_supply(), 950);
+
+            assert_eq!(contract.transfer(accounts.charlie, 501), Err(Error::InsufficientBalance));
+            assert_eq!(contract.transfer(accounts.charlie, 500), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn batch_transfer_rejects_amounts_that_overflow_the_total() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+
+            set_caller(accounts.bob);
+            let batch = vec![(accounts.charlie, u128::MAX), (accounts.django, 1)];
+            assert_eq!(contract.batch_transfer(batch), Err(Error::InvalidAmount));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+        }
+
+        #[ink::test]
+        fn contract_balance_reports_tokens_held_by_the_contract() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            assert_eq!(contract.contract_balance(), 0);
+
+            set_caller(accounts.bob);
+            contract.transfer(contract_id(), 30).unwrap();
+            assert_eq!(contract.contract_balance(), 30);
+            assert_eq!(contract.contract_balance(), contract.balance_of(contract_id()));
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_is_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.transfer(contract_id(), 50).unwrap();
+            assert_eq!(contract.emergency_withdraw(accounts.bob, 50), Err(Error::NotOwner));
+            assert_eq!(contract.contract_balance(), 50);
+        }
+
+        #[ink::test]
+        fn emergency_withdraw_moves_the_contracts_balance_while_paused() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.transfer(contract_id(), 50).unwrap();
+
+            set_caller(accounts.alice);
+            contract.pause().unwrap();
+            assert_eq!(contract.emergency_withdraw(accounts.charlie, 20), Ok(()));
+            assert_eq!(contract.contract_balance(), 30);
+            assert_eq!(contract.balance_of(accounts.charlie), 20);
+
+            let withdrawal = emitted::<EmergencyWithdrawal>().pop().unwrap();
+            assert_eq!((withdrawal.to, withdrawal.amount), (accounts.charlie, 20));
+
+            assert_eq!(contract.emergency_withdraw(accounts.charlie, 31), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn holders_page_returns_bounded_slices() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10);
+            contract.mint(accounts.charlie, 10).unwrap();
+            contract.mint(accounts.django, 10).unwrap();
+
+            assert_eq!(contract.holders_count(), 3);
+            assert_eq!(contract.holders_page(0, 2), vec![accounts.bob, accounts.charlie]);
+            assert_eq!(contract.holders_page(2, 2), vec![accounts.django]);
+            assert_eq!(contract.holders_page(1, u32::MAX), vec![accounts.charlie, accounts.django]);
+            assert!(contract.holders_page(3, 5).is_empty());
+            assert!(contract.holders_page(u32::MAX, u32::MAX).is_empty());
+            assert!(contract.holders_page(0, 0).is_empty());
+        }
+
+        #[ink::test]
+        fn holders_are_listed_once_and_removed_at_zero() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10);
+            contract.mint(accounts.bob, 10).unwrap();
+            contract.mint(accounts.charlie, 10).unwrap();
+            assert_eq!(contract.holders_page(0, 10), vec![accounts.bob, accounts.charlie]);
+
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 20).unwrap();
+            // The last holder moves into the freed slot
+            assert_eq!(contract.holders_count(), 1);
+            assert_eq!(contract.holders_page(0, 10), vec![accounts.charlie]);
+
+            set_caller(accounts.charlie);
+            contract.transfer(accounts.bob, 5).unwrap();
+            assert_eq!(contract.holders_page(0, 10), vec![accounts.charlie, accounts.bob]);
+        }
+
+        #[ink::test]
+        fn burn_fee_is_split_to_the_treasury() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_treasury(accounts.django).unwrap();
+            contract.set_burn_fee_bps(1_000).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(100), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 900);
+            assert_eq!(contract.balance_of(accounts.django), 10);
+            assert_eq!(contract.total_supply(), 910);
+            assert_eq!(contract.total_burned(), 90);
+
+            let burned = emitted::<TokensBurned>();
+            assert_eq!((burned[0].from, burned[0].amount), (accounts.bob, 90));
+            let transferred = emitted::<TokensTransferred>();
+            assert_eq!((transferred[0].from, transferred[0].to, transferred[0].amount), (accounts.bob, accounts.django, 10));
+        }
+
+        #[ink::test]
+        fn zero_burn_fee_and_dust_burns_are_burned_in_full() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_treasury(accounts.django).unwrap();
+
+            set_caller(accounts.bob);
+            contract.burn(100).unwrap();
+            assert_eq!(contract.total_burned(), 100);
+
+            set_caller(accounts.alice);
+            contract.set_burn_fee_bps(1_000).unwrap();
+
+            // 10% of 9 rounds down to zero
+            set_caller(accounts.bob);
+            contract.burn(9).unwrap();
+            assert_eq!(contract.total_burned(), 109);
+            assert_eq!(contract.balance_of(accounts.django), 0);
+            assert_eq!(contract.set_burn_fee_bps(10_001), Err(Error::NotOwner));
+
+            set_caller(accounts.alice);
+            assert_eq!(contract.set_burn_fee_bps(10_001), Err(Error::InvalidFee));
+        }
+
+        #[ink::test]
+        fn bridge_burns_pay_the_burn_fee() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            contract.set_treasury(accounts.django).unwrap();
+            contract.set_burn_fee_bps(1_000).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn_for_bridge(100, 7, [0xab; 32]), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 900);
+            assert_eq!(contract.balance_of(accounts.django), 10);
+            assert_eq!(contract.total_burned(), 90);
+
+            let burns = emitted::<BridgeBurn>();
+            assert_eq!((burns[0].from, burns[0].amount), (accounts.bob, 90));
+        }
+
+        #[ink::test]
+        fn are_blacklisted_reports_each_account_in_order() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+            contract.blacklist(accounts.charlie).unwrap();
+            contract.blacklist(accounts.eve).unwrap();
+
+            let statuses = contract.are_blacklisted(vec![accounts.bob, accounts.charlie, accounts.django, accounts.eve]);
+            assert_eq!(statuses, vec![false, true, false, true]);
+            assert!(contract.are_blacklisted(Vec::new()).is_empty());
+        }
+
+        #[ink::test]
+        fn transfer_checked_to_a_plain_account_behaves_like_transfer() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            // Contract recipients get a receiver hook call, which the off-chain environment cannot make
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer_checked(accounts.charlie, 40, vec![1, 2, 3]), Ok(()));
+            assert_eq!(contract.balance_of(accounts.bob), 60);
+            assert_eq!(contract.balance_of(accounts.charlie), 40);
+            assert_eq!(contract.transfer_checked(accounts.charlie, 61, Vec::new()), Err(Error::InsufficientBalance));
+        }
+
+        #[ink::test]
+        fn fee_exempt_accounts_skip_the_transfer_fee() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 10_000);
+            contract.set_fee_recipient(accounts.django).unwrap();
+            contract.set_fee_bps(1_000).unwrap();
+            contract.add_fee_exempt(accounts.eve).unwrap();
+            assert!(contract.is_fee_exempt(accounts.eve));
+
+            set_caller(accounts.bob);
+            // Exempt recipient
+            contract.transfer(accounts.eve, 100).unwrap();
+            assert_eq!(contract.balance_of(accounts.eve), 100);
+            // Normal transfer
+            contract.transfer(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.balance_of(accounts.charlie), 90);
+            assert_eq!(contract.balance_of(accounts.django), 10);
+
+            // Exempt sender
+            set_caller(accounts.eve);
+            contract.transfer(accounts.charlie, 100).unwrap();
+            assert_eq!(contract.balance_of(accounts.charlie), 190);
+
+            set_caller(accounts.alice);
+            contract.remove_fee_exempt(accounts.eve).unwrap();
+            assert!(!contract.is_fee_exempt(accounts.eve));
+        }
+
+        #[ink::test]
+        fn fee_exemptions_are_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.add_fee_exempt(accounts.bob), Err(Error::NotOwner));
+            assert!(!contract.is_fee_exempt(accounts.bob));
+        }
+
+        #[ink::test]
+        fn features_reflect_the_configuration() {
+            let mut contract = deploy_as(default_accounts().alice);
+            assert_eq!(contract.features(), FeatureFlags {
+                version: CONTRACT_VERSION,
+                minting: true,
+                multisig_minting: false,
+                faucet: false,
+                transfer_fee: false,
+                burn_fee: false,
+                transfer_confirmation: false,
+                transfer_cooldown: false,
+                circuit_breaker: false,
+            });
+
+            contract.set_faucet(10, 1).unwrap();
+            contract.set_fee_bps(5).unwrap();
+            contract.set_burn_fee_bps(5).unwrap();
+            contract.set_confirm_threshold(100).unwrap();
+            contract.set_transfer_cooldown_blocks(2).unwrap();
+            contract.set_outflow_threshold(Some(1_000)).unwrap();
+            contract.finish_minting().unwrap();
+            assert_eq!(contract.features(), FeatureFlags {
+                version: CONTRACT_VERSION,
+                minting: false,
+                multisig_minting: false,
+                faucet: true,
+                transfer_fee: true,
+                burn_fee: true,
+                transfer_confirmation: true,
+                transfer_cooldown: true,
+                circuit_breaker: true,
+            });
+
+            assert!(deploy_with_quorum().features().multisig_minting);
+        }
+
+        #[ink::test]
+        fn add_supply_stops_at_u128_max() {
+            let mut contract = deploy_as(default_accounts().alice);
+
+            assert_eq!(contract.add_supply(u128::MAX), Ok(()));
+            assert_eq!(contract.total_supply(), u128::MAX);
+            assert_eq!(contract.add_supply(1), Err(Error::InvalidAmount));
+            assert_eq!(contract.total_supply(), u128::MAX);
+            assert_eq!(contract.total_minted(), u128::MAX);
+        }
+
+        #[ink::test]
+        fn add_supply_rejects_an_overflowing_total_minted() {
+            let mut contract = deploy_as(default_accounts().alice);
+            contract.total_minted = u128::MAX;
+
+            assert_eq!(contract.add_supply(1), Err(Error::InvalidAmount));
+            assert_eq!(contract.total_supply(), 0);
+        }
+
+        #[ink::test]
+        fn sub_supply_stops_at_zero() {
+            let mut contract = deploy_as(default_accounts().alice);
+            contract.total_supply = u128::MAX;
+
+            assert_eq!(contract.sub_supply(u128::MAX), Ok(()));
+            assert_eq!(contract.total_supply(), 0);
+            assert_eq!(contract.total_burned(), u128::MAX);
+            assert_eq!(contract.sub_supply(1), Err(Error::SupplyUnderflow));
+        }
+
+        #[ink::test]
+        fn sub_supply_rejects_an_overflowing_total_burned() {
+            let mut contract = deploy_as(default_accounts().alice);
+            contract.total_supply = 10;
+            contract.total_burned = u128::MAX;
+
+            assert_eq!(contract.sub_supply(1), Err(Error::InvalidAmount));
+            assert_eq!(contract.total_supply(), 10);
+        }
+
+        #[ink::test]
+        fn allowances_of_owner_tracks_approvals() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+
+            set_caller(accounts.bob);
+            contract.approve(accounts.charlie, 10).unwrap();
+            contract.approve(accounts.django, 20).unwrap();
+            assert_eq!(contract.allowances_of_owner(accounts.bob), vec![(accounts.charlie, 10), (accounts.django, 20)]);
+
+            contract.approve(accounts.charlie, 15).unwrap();
+            assert_eq!(contract.allowances_of_owner(accounts.bob), vec![(accounts.charlie, 15), (accounts.django, 20)]);
+
+            contract.revoke_allowance(accounts.charlie).unwrap();
+            assert_eq!(contract.allowances_of_owner(accounts.bob), vec![(accounts.django, 20)]);
+
+            // Spending an allowance down to zero drops the spender too
+            set_caller(accounts.django);
+            contract.transfer_from(accounts.bob, accounts.eve, 20).unwrap();
+            assert!(contract.allowances_of_owner(accounts.bob).is_empty());
+            assert!(!contract.spenders.contains(accounts.bob));
+        }
+
+        #[ink::test]
+        fn new_with_supply_mints_to_the_deployer() {
+            let accounts = default_accounts();
+            test::set_callee::<DefaultEnvironment>(contract_id());
+            set_caller(accounts.alice);
+
+            let contract = TokenBalance::new_with_supply(1_000).unwrap();
+            assert_eq!(contract.balance_of(accounts.alice), 1_000);
+            assert_eq!(contract.total_supply(), 1_000);
+
+            let mints = emitted::<TokensMinted>();
+            assert_eq!(mints.len(), 1);
+            assert_eq!((mints[0].to, mints[0].amount, mints[0].minter), (accounts.alice, 1_000, accounts.alice));
+        }
+
+        #[ink::test]
+        fn new_with_zero_supply_mints_nothing() {
+            set_caller(default_accounts().alice);
+
+            let contract = TokenBalance::new_with_supply(0).unwrap();
+            assert_eq!(contract.total_supply(), 0);
+            assert!(emitted::<TokensMinted>().is_empty());
+        }
+
+        #[ink::test]
+        fn burns_while_paused_follow_burn_while_paused() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            contract.pause().unwrap();
+            assert!(contract.burn_while_paused());
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(10), Ok(()));
+
+            set_caller(accounts.alice);
+            contract.set_burn_while_paused(false).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(10), Err(Error::ContractPaused));
+            assert_eq!(contract.burn_for_bridge(10, 1, [0; 32]), Err(Error::ContractPaused));
+            assert_eq!(contract.balance_of(accounts.bob), 90);
+
+            set_caller(accounts.alice);
+            contract.add_pause_exempt(accounts.bob).unwrap();
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.burn(10), Ok(()));
+            assert_eq!(contract.set_burn_while_paused(true), Err(Error::NotOwner));
+        }
+
+        #[ink::test]
+        fn last_activity_updates_for_both_transfer_parties_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            assert_eq!(contract.last_activity_of(accounts.bob), Some(0));
+
+            test::set_block_timestamp::<DefaultEnvironment>(50);
+            set_caller(accounts.bob);
+            contract.transfer(accounts.charlie, 10).unwrap();
+            assert_eq!(contract.last_activity_of(accounts.bob), Some(50));
+            assert_eq!(contract.last_activity_of(accounts.charlie), Some(50));
+            assert_eq!(contract.last_activity_of(accounts.django), None);
+
+            test::set_block_timestamp::<DefaultEnvironment>(90);
+            contract.burn(5).unwrap();
+            assert_eq!(contract.last_activity_of(accounts.bob), Some(90));
+            assert_eq!(contract.last_activity_of(accounts.charlie), Some(50));
+        }
+
+        #[ink::test]
+        fn safe_approve_requires_a_reset_to_zero() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.safe_approve(accounts.charlie, 100), Ok(()));
+            assert_eq!(contract.safe_approve(accounts.charlie, 50), Err(Error::UnsafeApproval));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 100);
+
+            assert_eq!(contract.safe_approve(accounts.charlie, 0), Ok(()));
+            assert_eq!(contract.safe_approve(accounts.charlie, 50), Ok(()));
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 50);
+        }
+
+        #[ink::test]
+        fn combined_balance_is_the_local_balance_without_a_linked_token() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 100);
+            assert_eq!(contract.linked_token(), None);
+            assert_eq!(contract.combined_balance_of(accounts.bob), 100);
+
+            // Querying a linked token calls into it, which the off-chain environment cannot do
+            let sister = AccountId::from([0x77; 32]);
+            assert_eq!(contract.set_linked_token(Some(sister)), Ok(()));
+            assert_eq!(contract.linked_token(), Some(sister));
+
+            contract.set_linked_token(None).unwrap();
+            assert_eq!(contract.combined_balance_of(accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn linked_token_is_set_by_the_owner_only() {
+            let accounts = default_accounts();
+            let mut contract = deploy_as(accounts.alice);
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.set_linked_token(Some(accounts.charlie)), Err(Error::NotOwner));
+            assert_eq!(contract.linked_token(), None);
+        }
+
+        #[ink::test]
+        fn signed_batch_transfer_runs_once_per_nonce() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::bob();
+            let from = account_of(&signer);
+            let mut contract = deploy_with(from, 1_000);
+            let recipients: Vec<(AccountId, u128)> = vec![(accounts.charlie, 100), (accounts.django, 50)];
+            let signature = sign(&signer, &(BATCH_TRANSFER_DOMAIN, contract_id(), from, &recipients, 0u64));
+
+            // Anyone can relay the signed batch
+            set_caller(accounts.eve);
+            assert_eq!(contract.batch_transfer_signed(from, recipients.clone(), 0, signature), Ok(()));
+            assert_eq!(contract.balance_of(from), 850);
+            assert_eq!(contract.balance_of(accounts.charlie), 100);
+            assert_eq!(contract.batch_nonce(from), 1);
+
+            assert_eq!(contract.batch_transfer_signed(from, recipients, 0, signature), Err(Error::NonceReused));
+            assert_eq!(contract.balance_of(from), 850);
+        }
+
+        #[ink::test]
+        fn signed_batch_transfer_may_skip_ahead_in_nonces() {
+            let accounts = default_accounts();
+            let signer = ink_e2e::bob();
+            let from = account_of(&signer);
+            let mut contract = deploy_with(from, 1_000);
+            let recipients: Vec<(AccountId, u128)> = vec![(accounts.charlie, 100)];
+            let signature = sign(&signer, &(BATCH_TRANSFER_DOMAIN, contract_id(), from, &recipients, 5u64));
+
+            assert_eq!(contract.batch_transfer_signed(from, recipients, 5, signature), Ok(()));
+            assert_eq!(contract.batch_nonce(from), 6);
+        }
+
+        #[ink::test]
+        fn signed_batch_transfer_rejects_wrong_signatures() {
+            let accounts = default_accounts();
+            let from = account_of(&ink_e2e::bob());
+            let mut contract = deploy_with(from, 1_000);
+            let recipients: Vec<(AccountId, u128)> = vec![(accounts.charlie, 100)];
+
+            let by_someone_else = sign(&ink_e2e::charlie(), &(BATCH_TRANSFER_DOMAIN, contract_id(), from, &recipients, 0u64));
+            assert_eq!(
+                contract.batch_transfer_signed(from, recipients.clone(), 0, by_someone_else),
+                Err(Error::InvalidSignature)
+            );
+
+            let for_other_recipients = sign(&ink_e2e::bob(), &(BATCH_TRANSFER_DOMAIN, contract_id(), from, &recipients, 0u64));
+            let tampered = vec![(accounts.django, 100)];
+            assert_eq!(contract.batch_transfer_signed(from, tampered, 0, for_other_recipients), Err(Error::InvalidSignature));
+
+            assert_eq!(contract.balance_of(from), 1_000);
+            assert_eq!(contract.batch_nonce(from), 0);
+        }
+
+        #[ink::test]
+        fn zero_recipient_is_rejected_by_every_entry_point() {
+            let accounts = default_accounts();
+            let zero = AccountId::from([0; 32]);
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            assert_eq!(contract.mint(zero, 10), Err(Error::InvalidRecipient));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(zero, 10), Err(Error::InvalidRecipient));
+            assert_eq!(PSP22::transfer(&mut contract, zero, 10, Vec::new()), Err(PSP22Error::ZeroRecipientAddress));
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10), (zero, 10)]), Err(Error::InvalidRecipient));
+            assert_eq!(contract.batch_transfer_lenient(vec![(zero, 10)]), Ok(vec![false]));
+            contract.approve(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, zero, 10), Err(Error::InvalidRecipient));
+            assert_eq!(contract.balance_of(accounts.bob), 1_000);
+            assert_eq!(contract.allowance(accounts.bob, accounts.charlie), 100);
+        }
+
+        #[ink::test]
+        fn valid_recipients_are_still_accepted() {
+            let accounts = default_accounts();
+            let mut contract = deploy_with(accounts.bob, 1_000);
+            assert_eq!(contract.mint(accounts.charlie, 10), Ok(10));
+
+            set_caller(accounts.bob);
+            assert_eq!(contract.transfer(accounts.charlie, 10), Ok(()));
+            assert_eq!(contract.batch_transfer(vec![(accounts.charlie, 10), (accounts.django, 10)]), Ok(()));
+            contract.approve(accounts.charlie, 100).unwrap();
+
+            set_caller(accounts.charlie);
+            assert_eq!(contract.transfer_from(accounts.bob, accounts.django, 10), Ok(()));
+            assert_eq!(contract.balance_of(accounts.charlie), 30);
+            assert_eq!(contract.balance_of(accounts.django), 20);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use ink_e2e::ContractsBackend;
+        use mock_psp22::{MockPsp22, MockPsp22Ref};
+        use mock_receiver::{MockReceiver, MockReceiverRef};
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        #[ink_e2e::test]
+        async fn recover_token_transfers_the_foreign_token<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+
+            let mut mock_constructor = MockPsp22Ref::new();
+            let mock = client
+                .instantiate("mock_psp22", &ink_e2e::alice(), &mut mock_constructor)
+                .submit()
+                .await
+                .expect("mock instantiate failed");
+            let mock_call = mock.call_builder::<MockPsp22>();
+
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let recover = token_call.recover_token(mock.account_id, bob, 40);
+            let result = client.call(&ink_e2e::alice(), &recover).submit().await.expect("recover_token failed");
+            assert_eq!(result.return_value(), Ok(()));
+
+            let last_transfer = mock_call.last_transfer();
+            let recorded = client.call(&ink_e2e::alice(), &last_transfer).dry_run().await?.return_value();
+            assert_eq!(recorded, Some((bob, 40)));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn recover_token_reports_a_failing_transfer<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+
+            let mut mock_constructor = MockPsp22Ref::new();
+            let mock = client
+                .instantiate("mock_psp22", &ink_e2e::alice(), &mut mock_constructor)
+                .submit()
+                .await
+                .expect("mock instantiate failed");
+            let mut mock_call = mock.call_builder::<MockPsp22>();
+            client.call(&ink_e2e::alice(), &mock_call.set_fail(true)).submit().await.expect("set_fail failed");
+
+            let bob = ink_e2e::account_id(ink_e2e::AccountKeyring::Bob);
+            let recover = token_call.recover_token(mock.account_id, bob, 40);
+            let result = client.call(&ink_e2e::alice(), &recover).submit().await.expect("recover_token failed");
+            assert_eq!(result.return_value(), Err(Error::RecoveryFailed));
+
+            let last_transfer = mock_call.last_transfer();
+            let recorded = client.call(&ink_e2e::alice(), &last_transfer).dry_run().await?.return_value();
+            assert_eq!(recorded, None);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn transfer_checked_credits_an_accepting_receiver<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+            client.call(&ink_e2e::alice(), &token_call.mint(alice, 100)).submit().await.expect("mint failed");
+
+            let mut receiver_constructor = MockReceiverRef::new(true);
+            let receiver = client
+                .instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor)
+                .submit()
+                .await
+                .expect("receiver instantiate failed");
+            let receiver_call = receiver.call_builder::<MockReceiver>();
+
+            let transfer = token_call.transfer_checked(receiver.account_id, 40, vec![1, 2, 3]);
+            let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("transfer_checked failed");
+            assert_eq!(result.return_value(), Ok(()));
+
+            let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.account_id)).dry_run().await?;
+            assert_eq!(balance.return_value(), 40);
+            let last_received = client.call(&ink_e2e::alice(), &receiver_call.last_received()).dry_run().await?;
+            assert_eq!(last_received.return_value(), Some((alice, 40)));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn transfer_checked_reports_the_amount_after_fees<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let charlie = ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie);
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+            client.call(&ink_e2e::alice(), &token_call.mint(alice, 1_000)).submit().await.expect("mint failed");
+            client.call(&ink_e2e::alice(), &token_call.set_fee_recipient(charlie)).submit().await.expect("set_fee_recipient failed");
+            client.call(&ink_e2e::alice(), &token_call.set_fee_bps(1_000)).submit().await.expect("set_fee_bps failed");
+
+            let mut receiver_constructor = MockReceiverRef::new(true);
+            let receiver = client
+                .instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor)
+                .submit()
+                .await
+                .expect("receiver instantiate failed");
+            let receiver_call = receiver.call_builder::<MockReceiver>();
+
+            let transfer = token_call.transfer_checked(receiver.account_id, 100, Vec::new());
+            let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("transfer_checked failed");
+            assert_eq!(result.return_value(), Ok(()));
+
+            let balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.account_id)).dry_run().await?;
+            assert_eq!(balance.return_value(), 90);
+            let last_received = client.call(&ink_e2e::alice(), &receiver_call.last_received()).dry_run().await?;
+            assert_eq!(last_received.return_value(), Some((alice, 90)));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn transfer_checked_reverts_when_the_receiver_rejects<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+            client.call(&ink_e2e::alice(), &token_call.mint(alice, 100)).submit().await.expect("mint failed");
+
+            let mut receiver_constructor = MockReceiverRef::new(false);
+            let receiver = client
+                .instantiate("mock_receiver", &ink_e2e::alice(), &mut receiver_constructor)
+                .submit()
+                .await
+                .expect("receiver instantiate failed");
+
+            let transfer = token_call.transfer_checked(receiver.account_id, 40, Vec::new());
+            let result = client.call(&ink_e2e::alice(), &transfer).submit().await.expect("transfer_checked failed");
+            assert_eq!(result.return_value(), Err(Error::TransferRejected));
+
+            let sender_balance = client.call(&ink_e2e::alice(), &token_call.balance_of(alice)).dry_run().await?;
+            assert_eq!(sender_balance.return_value(), 100);
+            let receiver_balance = client.call(&ink_e2e::alice(), &token_call.balance_of(receiver.account_id)).dry_run().await?;
+            assert_eq!(receiver_balance.return_value(), 0);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn combined_balance_adds_the_linked_balance<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+            client.call(&ink_e2e::alice(), &token_call.mint(alice, 100)).submit().await.expect("mint failed");
+
+            let mut mock_constructor = MockPsp22Ref::new();
+            let mock = client
+                .instantiate("mock_psp22", &ink_e2e::alice(), &mut mock_constructor)
+                .submit()
+                .await
+                .expect("mock instantiate failed");
+            let mut mock_call = mock.call_builder::<MockPsp22>();
+            client.call(&ink_e2e::alice(), &mock_call.set_balance(50)).submit().await.expect("set_balance failed");
+            client
+                .call(&ink_e2e::alice(), &token_call.set_linked_token(Some(mock.account_id)))
+                .submit()
+                .await
+                .expect("set_linked_token failed");
+
+            let combined = client.call(&ink_e2e::alice(), &token_call.combined_balance_of(alice)).dry_run().await?;
+            assert_eq!(combined.return_value(), 150);
+
+            client.call(&ink_e2e::alice(), &mock_call.set_balance(u128::MAX)).submit().await.expect("set_balance failed");
+            let combined = client.call(&ink_e2e::alice(), &token_call.combined_balance_of(alice)).dry_run().await?;
+            assert_eq!(combined.return_value(), u128::MAX);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn combined_balance_falls_back_when_the_linked_token_fails<Client: E2EBackend>(
+            mut client: Client,
+        ) -> E2EResult<()> {
+            let alice = ink_e2e::account_id(ink_e2e::AccountKeyring::Alice);
+            let mut constructor = TokenBalanceRef::new();
+            let token = client
+                .instantiate("token_balance_contract", &ink_e2e::alice(), &mut constructor)
+                .submit()
+                .await
+                .expect("token instantiate failed");
+            let mut token_call = token.call_builder::<TokenBalance>();
+            client.call(&ink_e2e::alice(), &token_call.mint(alice, 100)).submit().await.expect("mint failed");
+
+            let mut mock_constructor = MockPsp22Ref::new();
+            let mock = client
+                .instantiate("mock_psp22", &ink_e2e::alice(), &mut mock_constructor)
+                .submit()
+                .await
+                .expect("mock instantiate failed");
+            let mut mock_call = mock.call_builder::<MockPsp22>();
+            client.call(&ink_e2e::alice(), &mock_call.set_balance(50)).submit().await.expect("set_balance failed");
+            client.call(&ink_e2e::alice(), &mock_call.set_fail(true)).submit().await.expect("set_fail failed");
+            client
+                .call(&ink_e2e::alice(), &token_call.set_linked_token(Some(mock.account_id)))
+                .submit()
+                .await
+                .expect("set_linked_token failed");
 
-            // Update caller's balance
-            let new_caller_balance = caller_balance.saturating_sub(total_amount);
-            self.balances.insert(caller, &new_caller_balance);
+            let combined = client.call(&ink_e2e::alice(), &token_call.combined_balance_of(alice)).dry_run().await?;
+            assert_eq!(combined.return_value(), 100);
 
             Ok(())
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,79 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_psp22::{MockPsp22, MockPsp22Ref};
+
+/// Minimal PSP22 token used by the e2e tests of `token_balance_contract`
+///
+/// Only the messages the token contract calls are implemented, under their
+/// standard `PSP22::<message>` selectors.
+#[ink::contract]
+mod mock_psp22 {
+    use ink::prelude::{string::String, vec::Vec};
+
+    /// Encodes like the `Custom` variant of the token's `PSP22Error`
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+    }
+
+    #[ink(storage)]
+    pub struct MockPsp22 {
+        balance: u128,
+        fail: bool,
+        last_transfer: Option<(AccountId, u128)>,
+    }
+
+    impl MockPsp22 {
+        /// Create a token reporting a zero balance for every account
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                balance: 0,
+                fail: false,
+                last_transfer: None,
+            }
+        }
+
+        /// Set the balance reported for every account
+        #[ink(message)]
+        pub fn set_balance(&mut self, balance: u128) {
+            self.balance = balance;
+        }
+
+        /// Make `balance_of` trap and `transfer` return an error
+        #[ink(message)]
+        pub fn set_fail(&mut self, fail: bool) {
+            self.fail = fail;
+        }
+
+        /// `PSP22::balance_of`
+        #[ink(message, selector = 0x6568382f)]
+        pub fn balance_of(&self, _owner: AccountId) -> u128 {
+            assert!(!self.fail, "balance_of failed");
+            self.balance
+        }
+
+        /// `PSP22::transfer`, recording its arguments instead of moving tokens
+        #[ink(message, selector = 0xdb20f9f5)]
+        pub fn transfer(&mut self, to: AccountId, value: u128, _data: Vec<u8>) -> Result<(), PSP22Error> {
+            if self.fail {
+                return Err(PSP22Error::Custom(String::from("TransferFailed")));
+            }
+            self.last_transfer = Some((to, value));
+            Ok(())
+        }
+
+        /// Get the `(to, value)` of the last successful transfer
+        #[ink(message)]
+        pub fn last_transfer(&self) -> Option<(AccountId, u128)> {
+            self.last_transfer
+        }
+    }
+
+    impl Default for MockPsp22 {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
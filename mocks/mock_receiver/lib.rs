@@ -0,0 +1,58 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_receiver::{MockReceiver, MockReceiverRef};
+
+/// `PSP22Receiver` used by the e2e tests of `token_balance_contract`
+///
+/// Accepts or rejects every `on_received` call depending on how it was
+/// instantiated, and records the last transfer it accepted.
+#[ink::contract]
+mod mock_receiver {
+    use ink::prelude::{string::String, vec::Vec};
+
+    /// Encodes like the `Custom` variant of the token's `PSP22Error`
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum PSP22Error {
+        Custom(String),
+    }
+
+    #[ink(storage)]
+    pub struct MockReceiver {
+        accept: bool,
+        last_received: Option<(AccountId, u128)>,
+    }
+
+    impl MockReceiver {
+        /// Create a receiver that accepts (`true`) or rejects every transfer
+        #[ink(constructor)]
+        pub fn new(accept: bool) -> Self {
+            Self {
+                accept,
+                last_received: None,
+            }
+        }
+
+        /// `PSP22Receiver::on_received`
+        #[ink(message, selector = 0x0305eeec)]
+        pub fn on_received(
+            &mut self,
+            _operator: AccountId,
+            from: AccountId,
+            value: u128,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            if !self.accept {
+                return Err(PSP22Error::Custom(String::from("Rejected")));
+            }
+            self.last_received = Some((from, value));
+            Ok(())
+        }
+
+        /// Get the `(from, value)` of the last accepted transfer
+        #[ink(message)]
+        pub fn last_received(&self) -> Option<(AccountId, u128)> {
+            self.last_received
+        }
+    }
+}